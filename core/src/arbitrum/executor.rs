@@ -2,13 +2,16 @@
 // Builds and submits flash loan arbitrage transactions
 
 use super::detector::{ArbitrageOpportunity, ArbitrageStep};
+use super::flashbots::FlashbotsClient;
 use super::pools::PoolType;
 use ethers::{
     prelude::*,
-    types::{Address, Bytes, U256, TransactionRequest},
+    types::{Address, Bytes, Eip1559TransactionRequest, U256, TransactionRequest},
     utils::keccak256,
 };
 use std::sync::Arc;
+use std::str::FromStr;
+use tokio::sync::RwLock;
 
 /// Flash arbitrage contract interface
 abigen!(
@@ -19,6 +22,17 @@ abigen!(
     ]"#
 );
 
+/// Arbitrum's `NodeInterface` precompile - not a real deployed contract, the
+/// node intercepts calls to this address and answers them itself.
+abigen!(
+    NodeInterface,
+    r#"[
+        function gasEstimateComponents(address to, bool contractCreation, bytes data) external payable returns (uint64 gasEstimate, uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate)
+    ]"#
+);
+
+const NODE_INTERFACE_ADDRESS: &str = "0x00000000000000000000000000000000000000C8";
+
 /// Executor configuration
 pub struct ExecutorConfig {
     pub contract_address: Address,
@@ -26,6 +40,56 @@ pub struct ExecutorConfig {
     pub max_gas_price: U256,
     pub priority_fee: U256,
     pub slippage_bps: u32,
+    /// `max_fee_per_gas = base_fee_per_gas * fee_multiplier_bps / 10_000 + priority_fee`,
+    /// giving the fee a cushion against base-fee increases across a few blocks
+    /// before the bundle lands. 20_000 = 2x base fee.
+    pub fee_multiplier_bps: u32,
+    /// Fold Arbitrum's L1 calldata-posting cost into `simulate()`'s `net_profit`
+    /// via the `NodeInterface` precompile. Leave off on chains without it.
+    pub da_gas_tracking: bool,
+    /// Flashbots-compatible relay URL for this chain, straight from
+    /// `ChainConfig::flashbots_relay` (`None` on chains without one, e.g. Arbitrum).
+    pub flashbots_relay: Option<String>,
+    /// Reputation key used to authenticate relay requests. Deliberately not
+    /// the same key as `private_key`, which only ever signs bundle transactions.
+    pub flashbots_signing_key: Option<LocalWallet>,
+}
+
+/// Resolved gas pricing for a transaction, chosen per-chain: EIP-1559 where the
+/// pending block exposes a `baseFeePerGas`, legacy `gasPrice` otherwise (some
+/// Arbitrum-family chains still reject type-2 transactions).
+#[derive(Debug, Clone, Copy)]
+enum FeeStrategy {
+    Eip1559 {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+        base_fee_per_gas: U256,
+    },
+    Legacy {
+        gas_price: U256,
+    },
+}
+
+impl FeeStrategy {
+    /// Price actually paid per gas unit at the current base fee, used for
+    /// profit accounting.
+    fn effective_gas_price(&self) -> U256 {
+        match self {
+            FeeStrategy::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas, base_fee_per_gas } => {
+                std::cmp::min(*max_fee_per_gas, base_fee_per_gas + max_priority_fee_per_gas)
+            }
+            FeeStrategy::Legacy { gas_price } => *gas_price,
+        }
+    }
+}
+
+/// `l1BaseFeeEstimate` only moves once per L1 block, so the latest value is
+/// cached alongside the L2 block number it was observed at. `estimate_l1_gas`
+/// still queries the precompile on every call and refreshes this cache.
+#[derive(Debug, Clone, Copy)]
+struct L1FeeCache {
+    l2_block: U64,
+    l1_base_fee_estimate: U256,
 }
 
 /// Arbitrage executor
@@ -33,6 +97,12 @@ pub struct ArbitrageExecutor {
     provider: Arc<Provider<Http>>,
     config: ExecutorConfig,
     balancer_vault: Address,
+    node_interface: Address,
+    l1_fee_cache: RwLock<Option<L1FeeCache>>,
+    /// `Some` when the chain's `ChainConfig` carries a relay URL and a
+    /// reputation key was configured; `submit_bundle` falls back to public
+    /// `execute` otherwise.
+    flashbots: Option<FlashbotsClient>,
 }
 
 impl ArbitrageExecutor {
@@ -40,93 +110,250 @@ impl ArbitrageExecutor {
         provider: Arc<Provider<Http>>,
         config: ExecutorConfig,
     ) -> Self {
-        use std::str::FromStr;
-        
+        let flashbots = match (&config.flashbots_relay, &config.flashbots_signing_key) {
+            (Some(relay_url), Some(signing_key)) => {
+                Some(FlashbotsClient::new(relay_url.clone(), signing_key.clone()))
+            }
+            _ => None,
+        };
+
         Self {
             provider,
             config,
             balancer_vault: Address::from_str("0xBA12222222228d8Ba445958a75a0704d566BF2C8").unwrap(),
+            node_interface: Address::from_str(NODE_INTERFACE_ADDRESS).unwrap(),
+            l1_fee_cache: RwLock::new(None),
+            flashbots,
         }
     }
-    
-    /// Execute an arbitrage opportunity
-    pub async fn execute(&self, opp: &ArbitrageOpportunity) -> Result<TxHash, ExecutorError> {
+
+    /// Resolve gas pricing for the next transaction: EIP-1559 when the pending
+    /// block reports a `baseFeePerGas`, falling back to legacy `gasPrice`
+    /// otherwise (chains in the Arbitrum family that still reject type-2 txs).
+    async fn resolve_fees(&self) -> Result<FeeStrategy, ExecutorError> {
+        let pending_block = self.provider.get_block(BlockNumber::Pending).await
+            .map_err(|e| ExecutorError::Provider(e.to_string()))?;
+
+        match pending_block.and_then(|b| b.base_fee_per_gas) {
+            Some(base_fee_per_gas) => {
+                let max_priority_fee_per_gas = self.config.priority_fee;
+                let max_fee_per_gas = base_fee_per_gas * U256::from(self.config.fee_multiplier_bps)
+                    / U256::from(10_000u64)
+                    + max_priority_fee_per_gas;
+                Ok(FeeStrategy::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas, base_fee_per_gas })
+            }
+            None => {
+                let gas_price = self.provider.get_gas_price().await
+                    .map_err(|e| ExecutorError::Provider(e.to_string()))?;
+                Ok(FeeStrategy::Legacy { gas_price })
+            }
+        }
+    }
+
+    /// Build the (unsigned) arbitrage transaction: calldata, slippage-protected
+    /// minimum output, and gas pricing. Shared by `execute` and `submit_bundle`
+    /// so both submission paths stay priced identically.
+    async fn build_arbitrage_tx(&self, opp: &ArbitrageOpportunity) -> Result<TypedTransaction, ExecutorError> {
         // Build calldata for our contract
         let user_data = self.encode_swap_path(&opp.path)?;
-        
+
         // Get tokens and amounts for flash loan
         let tokens = vec![opp.input_token];
         let amounts = vec![opp.input_amount];
-        
+
         // Apply slippage protection
         let min_output = opp.output_amount * (10000 - self.config.slippage_bps) / 10000;
-        
+
         // Build transaction
         let contract = FlashArbitrageContract::new(
             self.config.contract_address,
             self.provider.clone(),
         );
-        
+
         // Encode function call
         let call = contract.execute_arbitrage(tokens, amounts, user_data.into());
-        let tx = call.tx;
-        
-        // Get current gas price
-        let gas_price = self.provider.get_gas_price().await
-            .map_err(|e| ExecutorError::Provider(e.to_string()))?;
-        
-        if gas_price > self.config.max_gas_price {
-            return Err(ExecutorError::GasTooHigh(gas_price));
+        let data = call.tx.data().cloned().unwrap_or_default();
+
+        let fees = self.resolve_fees().await?;
+        if fees.effective_gas_price() > self.config.max_gas_price {
+            return Err(ExecutorError::GasTooHigh(fees.effective_gas_price()));
         }
-        
+
+        Ok(match fees {
+            FeeStrategy::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas, .. } => {
+                Eip1559TransactionRequest::new()
+                    .to(self.config.contract_address)
+                    .data(data)
+                    .max_fee_per_gas(max_fee_per_gas)
+                    .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                    .into()
+            }
+            FeeStrategy::Legacy { gas_price } => {
+                TransactionRequest::new()
+                    .to(self.config.contract_address)
+                    .data(data)
+                    .gas_price(gas_price)
+                    .into()
+            }
+        })
+    }
+
+    /// Execute an arbitrage opportunity by sending the signed transaction to
+    /// the public mempool.
+    pub async fn execute(&self, opp: &ArbitrageOpportunity) -> Result<TxHash, ExecutorError> {
+        let tx = self.build_arbitrage_tx(opp).await?;
+
         // Sign and send
         let wallet = self.config.private_key.clone()
             .with_chain_id(42161u64); // Arbitrum
-        
+
         let client = SignerMiddleware::new(self.provider.clone(), wallet);
-        
+
         let pending_tx = client.send_transaction(tx, None).await
             .map_err(|e| ExecutorError::Send(e.to_string()))?;
-        
+
         Ok(pending_tx.tx_hash())
     }
-    
+
+    /// Submit the arbitrage as a private bundle through the configured
+    /// Flashbots-compatible relay: sign the transaction, simulate it via
+    /// `eth_callBundle`, submit via `eth_sendBundle` targeting next block, then
+    /// poll `eth_getBundleStats` once for inclusion status. Falls back to
+    /// public `execute` when no relay is configured for this chain.
+    pub async fn submit_bundle(&self, opp: &ArbitrageOpportunity) -> Result<SubmissionResult, ExecutorError> {
+        let Some(flashbots) = &self.flashbots else {
+            let tx_hash = self.execute(opp).await?;
+            return Ok(SubmissionResult::Public(tx_hash));
+        };
+
+        let mut tx = self.build_arbitrage_tx(opp).await?;
+
+        let nonce = self.provider.get_transaction_count(self.config.private_key.address(), None).await
+            .map_err(|e| ExecutorError::Provider(e.to_string()))?;
+        tx.set_nonce(nonce);
+
+        let wallet = self.config.private_key.clone().with_chain_id(42161u64);
+        let signature = wallet.sign_transaction(&tx).await
+            .map_err(|e| ExecutorError::Send(e.to_string()))?;
+        let raw_signed = tx.rlp_signed(&signature);
+
+        // Decode the bytes we're about to submit and confirm they recover to
+        // our own wallet before paying a relay/gas cost on them — the same
+        // RLP decoder and ecrecover the mempool side uses to attribute a
+        // third party's tx, run here against our own.
+        let decoded = crate::ffi::hot_path::safe::decode_transaction(&raw_signed)
+            .ok_or_else(|| ExecutorError::Encoding("signed bundle tx failed to decode".into()))?;
+        if decoded.to != Some(self.config.contract_address) {
+            return Err(ExecutorError::Encoding("signed bundle tx `to` doesn't match the arbitrage contract".into()));
+        }
+        let recovered = crate::ffi::hot_path::safe::recover_sender(&raw_signed)
+            .ok_or_else(|| ExecutorError::SignatureMismatch)?;
+        if recovered != self.config.private_key.address() {
+            return Err(ExecutorError::SignatureMismatch);
+        }
+
+        let current_block = self.provider.get_block_number().await
+            .map_err(|e| ExecutorError::Provider(e.to_string()))?;
+        let target_block = current_block + 1;
+
+        let simulation = flashbots.call_bundle(&[raw_signed.clone()], target_block).await
+            .map_err(|e| ExecutorError::Simulation(e.to_string()))?;
+        if let Some(error) = simulation.get("error") {
+            return Err(ExecutorError::Simulation(error.to_string()));
+        }
+
+        let response = flashbots.send_bundle(&[raw_signed], target_block).await
+            .map_err(|e| ExecutorError::Send(e.to_string()))?;
+
+        let stats = flashbots.bundle_stats(&response.bundle_hash, target_block).await.ok();
+
+        Ok(SubmissionResult::Bundle {
+            bundle_hash: response.bundle_hash,
+            target_block,
+            stats,
+        })
+    }
+
     /// Simulate execution without sending
     pub async fn simulate(&self, opp: &ArbitrageOpportunity) -> Result<SimulationResult, ExecutorError> {
         let user_data = self.encode_swap_path(&opp.path)?;
         let tokens = vec![opp.input_token];
         let amounts = vec![opp.input_amount];
-        
+
         let contract = FlashArbitrageContract::new(
             self.config.contract_address,
             self.provider.clone(),
         );
-        
-        let call = contract.execute_arbitrage(tokens, amounts, user_data.into());
-        
+
+        let call = contract.execute_arbitrage(tokens.clone(), amounts.clone(), user_data.clone().into());
+
         // Estimate gas
         let gas_estimate = call.estimate_gas().await
             .map_err(|e| ExecutorError::Simulation(e.to_string()))?;
-        
-        let gas_price = self.provider.get_gas_price().await
-            .map_err(|e| ExecutorError::Provider(e.to_string()))?;
-        
-        let gas_cost = gas_estimate * gas_price;
+
+        let fees = self.resolve_fees().await?;
+        let effective_gas_price = fees.effective_gas_price();
+
+        let l2_gas_cost = gas_estimate * effective_gas_price;
+
+        let (l1_gas, l1_cost) = if self.config.da_gas_tracking {
+            let calldata = contract
+                .execute_arbitrage(tokens, amounts, user_data.into())
+                .calldata()
+                .unwrap_or_default();
+            self.estimate_l1_gas(calldata).await?
+        } else {
+            (U256::zero(), U256::zero())
+        };
+
+        let gas_cost = l2_gas_cost + l1_cost;
         let net_profit = if opp.profit > gas_cost {
             opp.profit - gas_cost
         } else {
             U256::zero()
         };
-        
+
         Ok(SimulationResult {
             success: true,
             gas_estimate,
             gas_cost,
             net_profit,
+            l1_gas,
+            l2_gas: gas_estimate,
             error: None,
         })
     }
-    
+
+    /// Query the `NodeInterface` precompile for the L1 calldata-posting cost of
+    /// `calldata` against our contract, returning `(gasEstimateForL1, l1_cost)`
+    /// where `l1_cost = gasEstimateForL1 * baseFee` — the L1 component is
+    /// already denominated in L2 gas units at the L2 base fee.
+    async fn estimate_l1_gas(&self, calldata: Bytes) -> Result<(U256, U256), ExecutorError> {
+        let node_interface = NodeInterface::new(self.node_interface, self.provider.clone());
+
+        let (_, gas_estimate_for_l1, base_fee, l1_base_fee_estimate) = node_interface
+            .gas_estimate_components(self.config.contract_address, false, calldata)
+            .call()
+            .await
+            .map_err(|e| ExecutorError::Provider(e.to_string()))?;
+
+        if let Ok(block_number) = self.provider.get_block_number().await {
+            let mut cache = self.l1_fee_cache.write().await;
+            *cache = Some(L1FeeCache { l2_block: block_number, l1_base_fee_estimate });
+        }
+
+        let l1_gas = U256::from(gas_estimate_for_l1);
+        Ok((l1_gas, l1_gas * base_fee))
+    }
+
+    /// Last `l1BaseFeeEstimate` observed via `estimate_l1_gas`, if any was
+    /// cached for the current L2 block.
+    pub async fn cached_l1_base_fee_estimate(&self) -> Option<U256> {
+        let cache = (*self.l1_fee_cache.read().await)?;
+        let current_block = self.provider.get_block_number().await.ok()?;
+        (cache.l2_block == current_block).then_some(cache.l1_base_fee_estimate)
+    }
+
     /// Encode swap path for contract
     fn encode_swap_path(&self, path: &[ArbitrageStep]) -> Result<Vec<u8>, ExecutorError> {
         use ethers::abi::{encode, Token};
@@ -168,6 +395,34 @@ impl ArbitrageExecutor {
                         Token::Uint(step.amount_out * 95 / 100),
                     ])
                 }
+                PoolType::StableSwap { amp, rates } => {
+                    // Type 4 = StableSwap, include amplification coefficient
+                    // plus both tokens' redemption rates (1e18 fixed point)
+                    encode(&[
+                        Token::Uint(U256::from(4)),
+                        Token::Address(step.pool),
+                        Token::Address(step.token_in),
+                        Token::Address(step.token_out),
+                        Token::Uint(U256::from(amp)),
+                        Token::Uint(rates[0]),
+                        Token::Uint(rates[1]),
+                        Token::Uint(step.amount_out * 95 / 100),
+                    ])
+                }
+                PoolType::LsdStable { amp, target_rate_bps, rate_token } => {
+                    // Type 5 = LSD StableSwap, include amp plus the rate
+                    // provider's token and current target rate
+                    encode(&[
+                        Token::Uint(U256::from(5)),
+                        Token::Address(step.pool),
+                        Token::Address(step.token_in),
+                        Token::Address(step.token_out),
+                        Token::Uint(U256::from(amp)),
+                        Token::Address(rate_token),
+                        Token::Uint(U256::from(target_rate_bps)),
+                        Token::Uint(step.amount_out * 95 / 100),
+                    ])
+                }
             };
             encoded_steps.push(dex_type);
         }
@@ -187,9 +442,27 @@ pub struct SimulationResult {
     pub gas_estimate: U256,
     pub gas_cost: U256,
     pub net_profit: U256,
+    /// L1 calldata-posting gas (in L2 gas units), zero unless `da_gas_tracking` is on.
+    pub l1_gas: U256,
+    /// L2 execution gas, i.e. `gas_estimate`.
+    pub l2_gas: U256,
     pub error: Option<String>,
 }
 
+/// Outcome of `submit_bundle`.
+#[derive(Debug)]
+pub enum SubmissionResult {
+    /// No relay configured for this chain; sent to the public mempool instead.
+    Public(TxHash),
+    /// Accepted by the Flashbots-compatible relay.
+    Bundle {
+        bundle_hash: String,
+        target_block: U64,
+        /// `eth_getBundleStats` response, if the relay answered.
+        stats: Option<serde_json::Value>,
+    },
+}
+
 #[derive(Debug)]
 pub enum ExecutorError {
     Provider(String),
@@ -198,6 +471,9 @@ pub enum ExecutorError {
     GasTooHigh(U256),
     Send(String),
     NotProfitable,
+    /// The freshly-signed bundle tx didn't decode back to the sender we
+    /// signed with — see `submit_bundle`'s pre-submission check.
+    SignatureMismatch,
 }
 
 impl std::fmt::Display for ExecutorError {
@@ -209,6 +485,7 @@ impl std::fmt::Display for ExecutorError {
             ExecutorError::GasTooHigh(price) => write!(f, "Gas too high: {:?}", price),
             ExecutorError::Send(e) => write!(f, "Failed to send: {}", e),
             ExecutorError::NotProfitable => write!(f, "Not profitable after gas"),
+            ExecutorError::SignatureMismatch => write!(f, "Signed bundle tx recovered to an unexpected sender"),
         }
     }
 }
@@ -0,0 +1,104 @@
+// Flashbots-compatible private bundle relay client
+//
+// `ArbitrageExecutor::execute` used to hand signed transactions straight to
+// `send_transaction`, i.e. the public mempool, which is exactly where a
+// profitable arbitrage gets front-run. This talks to a Flashbots-style relay
+// instead (`eth_sendBundle` / `eth_callBundle` / `eth_getBundleStats`) so the
+// bundle only lands exactly as submitted or not at all.
+
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::{Bytes, U64};
+use ethers::utils::keccak256;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Talks to one Flashbots-compatible relay. Requests are authenticated with a
+/// reputation key that is deliberately separate from whatever key signs the
+/// bundle's transactions.
+pub struct FlashbotsClient {
+    relay_url: String,
+    http: reqwest::Client,
+    reputation_key: LocalWallet,
+}
+
+impl FlashbotsClient {
+    pub fn new(relay_url: String, reputation_key: LocalWallet) -> Self {
+        Self { relay_url, http: reqwest::Client::new(), reputation_key }
+    }
+
+    /// Submit a bundle of raw signed transactions targeting `target_block`.
+    pub async fn send_bundle(&self, signed_txs: &[Bytes], target_block: U64) -> anyhow::Result<BundleResponse> {
+        let params = json!([{
+            "txs": encode_txs(signed_txs),
+            "blockNumber": encode_block_number(target_block),
+        }]);
+        self.call("eth_sendBundle", params).await
+    }
+
+    /// Simulate a bundle against `target_block` without submitting it.
+    pub async fn call_bundle(&self, signed_txs: &[Bytes], target_block: U64) -> anyhow::Result<Value> {
+        let params = json!([{
+            "txs": encode_txs(signed_txs),
+            "blockNumber": encode_block_number(target_block),
+            "stateBlockNumber": "latest",
+        }]);
+        self.call("eth_callBundle", params).await
+    }
+
+    /// Poll relay-side inclusion stats for a previously submitted bundle.
+    pub async fn bundle_stats(&self, bundle_hash: &str, target_block: U64) -> anyhow::Result<Value> {
+        let params = json!([{
+            "bundleHash": bundle_hash,
+            "blockNumber": encode_block_number(target_block),
+        }]);
+        self.call("eth_getBundleStats", params).await
+    }
+
+    /// POST one JSON-RPC call to the relay, signed per the Flashbots auth
+    /// scheme: `X-Flashbots-Signature: <address>:<personal_sign(keccak256(body))>`.
+    async fn call<T: for<'de> Deserialize<'de>>(&self, method: &str, params: Value) -> anyhow::Result<T> {
+        let body = serde_json::to_vec(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))?;
+
+        let signature = self.reputation_key.sign_message(keccak256(&body)).await?;
+        let header = format!("{:?}:0x{}", self.reputation_key.address(), hex::encode(signature.to_vec()));
+
+        let response = self.http.post(&self.relay_url)
+            .header("Content-Type", "application/json")
+            .header("X-Flashbots-Signature", header)
+            .body(body)
+            .send()
+            .await?;
+
+        let parsed: RpcResponse<T> = response.json().await?;
+        match parsed.result {
+            Some(result) => Ok(result),
+            None => anyhow::bail!("relay returned an error for {}: {:?}", method, parsed.error),
+        }
+    }
+}
+
+fn encode_txs(signed_txs: &[Bytes]) -> Vec<String> {
+    signed_txs.iter().map(|tx| format!("0x{}", hex::encode(tx.as_ref()))).collect()
+}
+
+fn encode_block_number(block: U64) -> String {
+    format!("0x{:x}", block.as_u64())
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+/// `eth_sendBundle` response payload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BundleResponse {
+    #[serde(rename = "bundleHash")]
+    pub bundle_hash: String,
+}
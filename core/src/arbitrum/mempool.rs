@@ -0,0 +1,211 @@
+//! Pending-swap mempool monitor for Arbitrum
+//!
+//! `PoolManager` only refreshes reserves on demand (from `ArbitrageDetector`'s
+//! own polling loop), so it never reacts to a trade that's still in-flight —
+//! a quote taken right before a large pending swap lands is already stale by
+//! the time it's used. This subscribes to the chain's pending-transaction
+//! feed over `ArbitrumConfig::ws_url`, decodes calldata aimed at the tracked
+//! routers (Uniswap V3 / SushiSwap / Camelot), and resolves each decoded
+//! swap to the `Pool`(s) it trades against. On a match it eagerly
+//! `refresh_pool`s the affected pools and emits a `PendingSwap` on a
+//! broadcast channel, for the detector/executor side (backrun and sandwich
+//! construction) to subscribe to independently.
+
+use crate::arbitrum::pools::{Pool, PoolManager, PoolType};
+use crate::arbitrum::{ArbitrumConfig, ArbitrumDexes};
+use dashmap::DashMap;
+use ethers::contract::{abigen, EthCall};
+use ethers::providers::{Middleware, Provider, StreamExt, Ws};
+use ethers::types::{Address, Transaction, H256, U256};
+use futures_util::StreamExt as _;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+abigen!(
+    UniswapV2RouterSwap,
+    r#"[
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts)
+    ]"#
+);
+
+abigen!(
+    UniswapV3RouterSwap,
+    r#"[
+        function exactInputSingle((address tokenIn, address tokenOut, uint24 fee, address recipient, uint256 deadline, uint256 amountIn, uint256 amountOutMinimum, uint160 sqrtPriceLimitX96) params) external payable returns (uint256 amountOut)
+    ]"#
+);
+
+/// A pending swap, decoded from mempool calldata and resolved to the `Pool`
+/// it will trade against. `victim_tx_hash` is what a backrun/sandwich
+/// builder anchors its bundle to.
+#[derive(Debug, Clone)]
+pub struct PendingSwap {
+    pub pool: Address,
+    pub token_in: Address,
+    pub amount_in: U256,
+    pub victim_tx_hash: H256,
+}
+
+/// Which router decoded the swap, used to pick the matching `PoolType` when
+/// resolving to a tracked pool (the same token pair can have both a Sushi
+/// and a Camelot pool, and only one of them is what this tx actually trades
+/// against).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RouterDex {
+    Sushi,
+    Camelot,
+    UniswapV3,
+}
+
+struct DecodedSwap {
+    dex: RouterDex,
+    token_in: Address,
+    token_out: Address,
+    amount_in: U256,
+    fee: Option<u32>,
+}
+
+/// Initial reconnect backoff, doubled per consecutive dropped connection up
+/// to `MAX_BACKOFF`.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Dedup entries older than this are pruned, so a reconnect replaying
+/// recently-seen transactions doesn't grow the set without bound.
+const DEDUP_TTL: Duration = Duration::from_secs(120);
+
+pub struct PendingSwapMonitor {
+    ws_url: String,
+    dexes: ArbitrumDexes,
+    pool_manager: Arc<PoolManager>,
+    sender: broadcast::Sender<PendingSwap>,
+    seen: DashMap<H256, Instant>,
+}
+
+impl PendingSwapMonitor {
+    pub fn new(config: &ArbitrumConfig, pool_manager: Arc<PoolManager>) -> Self {
+        let (sender, _) = broadcast::channel(4096);
+        Self {
+            ws_url: config.ws_url.clone(),
+            dexes: ArbitrumDexes::default(),
+            pool_manager,
+            sender,
+            seen: DashMap::new(),
+        }
+    }
+
+    /// Independent subscription onto decoded `PendingSwap` events — every
+    /// consumer (backrun detector, sandwich detector, ...) gets its own
+    /// receiver so a slow one only falls behind its own subscription.
+    pub fn subscribe(&self) -> broadcast::Receiver<PendingSwap> {
+        self.sender.subscribe()
+    }
+
+    /// Runs forever, reconnecting with exponential backoff whenever the WS
+    /// subscription drops (dropped connection, RPC restart, etc.) rather
+    /// than giving up.
+    pub async fn run(&self) {
+        let mut consecutive_failures = 0u32;
+        loop {
+            if let Err(e) = self.run_once().await {
+                let backoff = INITIAL_BACKOFF
+                    .checked_mul(1u32 << consecutive_failures.min(5))
+                    .unwrap_or(MAX_BACKOFF)
+                    .min(MAX_BACKOFF);
+                warn!("pending swap monitor disconnected: {}; reconnecting in {:?}", e, backoff);
+                consecutive_failures += 1;
+                tokio::time::sleep(backoff).await;
+            } else {
+                consecutive_failures = 0;
+            }
+        }
+    }
+
+    async fn run_once(&self) -> anyhow::Result<()> {
+        let provider = Provider::<Ws>::connect(&self.ws_url).await?;
+        let mut stream = provider.subscribe_pending_txs().await?.transactions_unordered(32);
+
+        while let Some(tx) = stream.next().await {
+            let tx = match tx {
+                Ok(tx) => tx,
+                // Dropped from the mempool (replaced/mined) before we could
+                // fetch its body — nothing to decode.
+                Err(_) => continue,
+            };
+            self.handle_tx(tx).await;
+        }
+
+        anyhow::bail!("pending transaction subscription stream ended")
+    }
+
+    async fn handle_tx(&self, tx: Transaction) {
+        if self.seen.contains_key(&tx.hash) {
+            return;
+        }
+        self.prune_seen();
+        self.seen.insert(tx.hash, Instant::now());
+
+        let Some(to) = tx.to else { return };
+        let Some(swap) = self.decode_swap(to, tx.input.as_ref()) else { return };
+
+        for pool in self.pool_manager.get_pools(swap.token_in, swap.token_out).await {
+            if !dex_matches(&pool, swap.dex, swap.fee) {
+                continue;
+            }
+
+            self.pool_manager.refresh_pool(pool.address).await;
+            let _ = self.sender.send(PendingSwap {
+                pool: pool.address,
+                token_in: swap.token_in,
+                amount_in: swap.amount_in,
+                victim_tx_hash: tx.hash,
+            });
+        }
+    }
+
+    /// Decodes `input` against whichever router ABI `to` belongs to.
+    /// Returns `None` for calldata this isn't one of the tracked routers'
+    /// swap functions — most mempool traffic, including this one.
+    fn decode_swap(&self, to: Address, input: &[u8]) -> Option<DecodedSwap> {
+        if to == self.dexes.sushi_router || to == self.dexes.camelot_router {
+            let call = SwapExactTokensForTokensCall::decode(input).ok()?;
+            let token_in = *call.path.first()?;
+            let token_out = *call.path.last()?;
+            let dex = if to == self.dexes.sushi_router { RouterDex::Sushi } else { RouterDex::Camelot };
+            return Some(DecodedSwap { dex, token_in, token_out, amount_in: call.amount_in, fee: None });
+        }
+
+        if to == self.dexes.uniswap_v3_router {
+            let call = ExactInputSingleCall::decode(input).ok()?;
+            return Some(DecodedSwap {
+                dex: RouterDex::UniswapV3,
+                token_in: call.params.token_in,
+                token_out: call.params.token_out,
+                amount_in: call.params.amount_in,
+                fee: Some(call.params.fee),
+            });
+        }
+
+        None
+    }
+
+    /// Drops dedup entries older than `DEDUP_TTL`. Called on every new tx
+    /// rather than on a timer, since there's no background task otherwise
+    /// driving this monitor's own upkeep.
+    fn prune_seen(&self) {
+        let cutoff = Instant::now() - DEDUP_TTL;
+        self.seen.retain(|_, seen_at| *seen_at >= cutoff);
+    }
+}
+
+/// Whether `pool` is the venue `dex` actually trades through — same token
+/// pair, matching pool type (and, for V3, matching fee tier).
+fn dex_matches(pool: &Pool, dex: RouterDex, fee: Option<u32>) -> bool {
+    match (&pool.pool_type, dex) {
+        (PoolType::SushiSwap, RouterDex::Sushi) => true,
+        (PoolType::Camelot, RouterDex::Camelot) => true,
+        (PoolType::UniswapV3 { fee: pool_fee }, RouterDex::UniswapV3) => Some(*pool_fee) == fee,
+        _ => false,
+    }
+}
@@ -3,6 +3,8 @@
 pub mod pools;
 pub mod detector;
 pub mod executor;
+pub mod flashbots;
+pub mod mempool;
 
 use ethers::types::{Address, U256};
 use std::str::FromStr;
@@ -3,13 +3,18 @@
 
 use ethers::{
     prelude::*,
-    types::{Address, H256, U256},
+    types::{Address, H256, U256, TransactionRequest, Filter, Log},
     providers::{Provider, Http},
     contract::abigen,
+    abi::{decode, encode, ParamType, Token},
 };
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::simulator::call_view;
 
 // Generate contract bindings
 abigen!(
@@ -37,6 +42,7 @@ abigen!(
         function getPair(address tokenA, address tokenB) external view returns (address pair)
         function allPairs(uint256) external view returns (address pair)
         function allPairsLength() external view returns (uint256)
+        event PairCreated(address indexed token0, address indexed token1, address pair, uint256)
     ]"#
 );
 
@@ -55,6 +61,49 @@ pub enum PoolType {
     UniswapV3 { fee: u32 },
     SushiSwap,
     Camelot,
+    /// Curve-style StableSwap pool, priced off the amplified invariant
+    /// instead of a constant product — what stablecoin/LSD pairs actually
+    /// trade against on Arbitrum. `rates[0]`/`rates[1]` are each token's
+    /// redemption rate in 1e18 fixed point (`wad()` for a plain 1:1 stable
+    /// pair like USDC/USDT); `get_amount_out` pre-scales both balances by
+    /// their rate before running the invariant and un-scales the result, so
+    /// pegged-but-not-1:1 pairs (e.g. wstETH/WETH) price correctly without
+    /// needing a separate pool type.
+    StableSwap { amp: u64, rates: [U256; 2] },
+    /// StableSwap pool for a rebasing liquid-staking-derivative like
+    /// wstETH or rETH, where `rate_token`'s balance drifts against its
+    /// pair purely from accruing staking rewards. `target_rate_bps` is the
+    /// externally polled derivative/underlying exchange rate (10_000 = 1:1);
+    /// `get_amount_out` scales `rate_token`'s balance by it before running
+    /// the invariant math and unscales the output, so the raw reserve ratio
+    /// drifting with staking rewards doesn't look like a permanent arb.
+    LsdStable { amp: u64, target_rate_bps: u64, rate_token: Address },
+}
+
+/// Which of `Pool::get_amount_out`'s analytic formula or `PoolManager`'s
+/// forked-EVM simulation should be trusted for a given pool, tagged by
+/// `discover_pools` per pool type. `UniswapV3`'s virtual-reserve trick is a
+/// V2-curve approximation that's wrong across tick boundaries, and
+/// `Camelot`'s pair contract applies a dynamic directional fee the flat
+/// `fee_bps` can't capture — both need `Simulated` for an exact quote.
+/// `SushiSwap` is a plain constant-product pair and `StableSwap`/`LsdStable`
+/// are priced by the same invariant math their real contracts use, so the
+/// analytic path is already exact for them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteMode {
+    Analytic,
+    Simulated,
+}
+
+impl QuoteMode {
+    fn for_pool_type(pool_type: &PoolType) -> Self {
+        match pool_type {
+            PoolType::UniswapV3 { .. } | PoolType::Camelot => QuoteMode::Simulated,
+            PoolType::SushiSwap | PoolType::StableSwap { .. } | PoolType::LsdStable { .. } => {
+                QuoteMode::Analytic
+            }
+        }
+    }
 }
 
 /// Unified pool representation
@@ -68,30 +117,167 @@ pub struct Pool {
     pub reserve1: U256,
     pub liquidity: U256,
     pub fee_bps: u32, // in basis points (30 = 0.30%)
+    /// Which quoting path (`Pool::get_amount_out` vs
+    /// `PoolManager::quote_exact_in`'s simulated backend) is trustworthy for
+    /// this pool. Set once at discovery from `pool_type`; see `QuoteMode`.
+    pub quote_mode: QuoteMode,
 }
 
 impl Pool {
-    /// Calculate output amount for V2-style pools
+    /// Calculate output amount, dispatching on pool type.
     pub fn get_amount_out(&self, amount_in: U256, token_in: Address) -> U256 {
+        match self.pool_type {
+            PoolType::StableSwap { amp, rates } => self.stable_amount_out(amount_in, token_in, amp, rates),
+            PoolType::LsdStable { amp, target_rate_bps, rate_token } => {
+                self.lsd_stable_amount_out(amount_in, token_in, amp, target_rate_bps, rate_token)
+            }
+            PoolType::UniswapV3 { .. } | PoolType::SushiSwap | PoolType::Camelot => {
+                self.constant_product_amount_out(amount_in, token_in)
+            }
+        }
+    }
+
+    /// Constant-product (x*y=k) output, used by the V2/V3-style pool types.
+    fn constant_product_amount_out(&self, amount_in: U256, token_in: Address) -> U256 {
         let (reserve_in, reserve_out) = if token_in == self.token0 {
             (self.reserve0, self.reserve1)
         } else {
             (self.reserve1, self.reserve0)
         };
-        
+
         if reserve_in.is_zero() || reserve_out.is_zero() {
             return U256::zero();
         }
-        
+
         // AMM formula: amount_out = (amount_in * fee_factor * reserve_out) / (reserve_in + amount_in * fee_factor)
         let fee_factor = 10000 - self.fee_bps;
         let amount_in_with_fee = amount_in * fee_factor;
         let numerator = amount_in_with_fee * reserve_out;
         let denominator = reserve_in * 10000 + amount_in_with_fee;
-        
+
         numerator / denominator
     }
-    
+
+    /// StableSwap (Curve-style) output for this 2-asset pool: pre-scale both
+    /// balances by their `rates` (a plain 1:1 pair passes `[wad(), wad()]` and
+    /// the scaling is a no-op), hold the invariant `D` fixed across the
+    /// trade, solve the single-coin quadratic for the new output balance,
+    /// then un-scale back to raw token units. Fee is applied to the input
+    /// exactly as the constant-product path does.
+    fn stable_amount_out(&self, amount_in: U256, token_in: Address, amp: u64, rates: [U256; 2]) -> U256 {
+        let (balance_in, balance_out, rate_in, rate_out) = if token_in == self.token0 {
+            (self.reserve0, self.reserve1, rates[0], rates[1])
+        } else if token_in == self.token1 {
+            (self.reserve1, self.reserve0, rates[1], rates[0])
+        } else {
+            return U256::zero();
+        };
+
+        if balance_in.is_zero() || balance_out.is_zero() || rate_in.is_zero() || rate_out.is_zero() {
+            return U256::zero();
+        }
+
+        let fee_factor = 10_000 - self.fee_bps;
+        let amount_in_with_fee = amount_in * fee_factor / 10_000;
+        if amount_in_with_fee.is_zero() {
+            return U256::zero();
+        }
+
+        let scaled_balance_in = balance_in * rate_in / wad();
+        let scaled_balance_out = balance_out * rate_out / wad();
+        let scaled_amount_in = amount_in_with_fee * rate_in / wad();
+        if scaled_balance_in.is_zero() || scaled_balance_out.is_zero() || scaled_amount_in.is_zero() {
+            return U256::zero();
+        }
+
+        let d = stable_get_d(&[scaled_balance_in, scaled_balance_out], amp);
+        if d.is_zero() {
+            return U256::zero();
+        }
+
+        let new_scaled_balance_in = scaled_balance_in + scaled_amount_in;
+        let Some(new_scaled_balance_out) = stable_get_y(d, amp, 2, &[new_scaled_balance_in]) else {
+            return U256::zero();
+        };
+
+        // Round down by one to stay conservative, per the StableSwap convention.
+        if new_scaled_balance_out + U256::from(1) >= scaled_balance_out {
+            return U256::zero();
+        }
+        let scaled_amount_out = scaled_balance_out - new_scaled_balance_out - U256::from(1);
+
+        scaled_amount_out * wad() / rate_out
+    }
+
+    /// StableSwap output for an LSD pair: scale `rate_token`'s balance (and
+    /// any input/output denominated in it) by `target_rate_bps` before
+    /// running the same invariant math as `stable_amount_out`, then unscale
+    /// the result. This is what keeps wstETH/rETH pools from looking like a
+    /// permanent arbitrage as the derivative accrues staking rewards.
+    fn lsd_stable_amount_out(
+        &self,
+        amount_in: U256,
+        token_in: Address,
+        amp: u64,
+        target_rate_bps: u64,
+        rate_token: Address,
+    ) -> U256 {
+        if target_rate_bps == 0 {
+            return U256::zero();
+        }
+
+        let (balance_in, balance_out, token_out) = if token_in == self.token0 {
+            (self.reserve0, self.reserve1, self.token1)
+        } else if token_in == self.token1 {
+            (self.reserve1, self.reserve0, self.token0)
+        } else {
+            return U256::zero();
+        };
+
+        if balance_in.is_zero() || balance_out.is_zero() {
+            return U256::zero();
+        }
+
+        let fee_factor = 10_000 - self.fee_bps;
+        let amount_in_with_fee = amount_in * fee_factor / 10_000;
+        if amount_in_with_fee.is_zero() {
+            return U256::zero();
+        }
+
+        let rate = U256::from(target_rate_bps);
+        let scale = |amount: U256, token: Address| -> U256 {
+            if token == rate_token { amount * rate / U256::from(10_000) } else { amount }
+        };
+
+        let scaled_balance_in = scale(balance_in, token_in);
+        let scaled_balance_out = scale(balance_out, token_out);
+        let scaled_amount_in = scale(amount_in_with_fee, token_in);
+        if scaled_balance_in.is_zero() || scaled_balance_out.is_zero() || scaled_amount_in.is_zero() {
+            return U256::zero();
+        }
+
+        let d = stable_get_d(&[scaled_balance_in, scaled_balance_out], amp);
+        if d.is_zero() {
+            return U256::zero();
+        }
+
+        let new_scaled_balance_in = scaled_balance_in + scaled_amount_in;
+        let Some(new_scaled_balance_out) = stable_get_y(d, amp, 2, &[new_scaled_balance_in]) else {
+            return U256::zero();
+        };
+
+        if new_scaled_balance_out + U256::from(1) >= scaled_balance_out {
+            return U256::zero();
+        }
+        let scaled_amount_out = scaled_balance_out - new_scaled_balance_out - U256::from(1);
+
+        if token_out == rate_token {
+            scaled_amount_out * U256::from(10_000) / rate
+        } else {
+            scaled_amount_out
+        }
+    }
+
     /// Get price of token0 in terms of token1
     pub fn get_price(&self) -> f64 {
         if self.reserve0.is_zero() {
@@ -104,242 +290,417 @@ impl Pool {
     }
 }
 
+/// 1e18 fixed point, used to express a `StableSwap` rate of exactly 1:1.
+fn wad() -> U256 {
+    U256::from(1_000_000_000_000_000_000u64)
+}
+
+/// Newton iteration for the StableSwap invariant `D`, given every token
+/// `balances` and amplification `amp` (`Ann = amp * n^n`). Returns zero if
+/// any balance is zero or the iteration doesn't converge to a sane value.
+fn stable_get_d(balances: &[U256], amp: u64) -> U256 {
+    let n = balances.len();
+    if n == 0 || balances.iter().any(|b| b.is_zero()) {
+        return U256::zero();
+    }
+
+    let n_u256 = U256::from(n as u64);
+    let ann = U256::from(amp) * n_u256.pow(n_u256);
+    let s: U256 = balances.iter().fold(U256::zero(), |acc, b| acc + b);
+
+    let mut d = s;
+    for _ in 0..255 {
+        let mut d_p = d;
+        for b in balances {
+            d_p = d_p * d / (*b * n_u256);
+        }
+
+        let d_prev = d;
+        let numerator = (ann * s + d_p * n_u256) * d;
+        let denominator = (ann - U256::from(1)) * d + (n_u256 + U256::from(1)) * d_p;
+        if denominator.is_zero() {
+            return U256::zero();
+        }
+        d = numerator / denominator;
+
+        let diff = if d > d_prev { d - d_prev } else { d_prev - d };
+        if diff <= U256::from(1) {
+            return d;
+        }
+    }
+
+    U256::zero()
+}
+
+/// Solve the single-coin StableSwap equation `y = (y² + c) / (2y + b − D)`
+/// for the new balance of the coin being solved for, holding `d` and `amp`
+/// fixed. `known_balances` is every other coin's balance (already reflecting
+/// the trade); `n` is the total number of coins in the pool. Returns `None`
+/// if the iteration doesn't converge or the domain guard would underflow.
+fn stable_get_y(d: U256, amp: u64, n: usize, known_balances: &[U256]) -> Option<U256> {
+    let n_u256 = U256::from(n as u64);
+    let ann = U256::from(amp) * n_u256.pow(n_u256);
+    if ann.is_zero() || known_balances.iter().any(|b| b.is_zero()) {
+        return None;
+    }
+
+    let s: U256 = known_balances.iter().fold(U256::zero(), |acc, b| acc + b);
+    let mut c = d;
+    for b in known_balances {
+        c = c * d / (*b * n_u256);
+    }
+    c = c * d / (ann * n_u256);
+    let b_term = s + d / ann;
+
+    let mut y = d;
+    for _ in 0..255 {
+        let y_prev = y;
+        let two_y_plus_b = U256::from(2) * y + b_term;
+        if two_y_plus_b < d {
+            return None; // out of the algorithm's valid domain — bail rather than underflow
+        }
+        let denominator = two_y_plus_b - d;
+        if denominator.is_zero() {
+            return None;
+        }
+        y = (y * y + c) / denominator;
+
+        let diff = if y > y_prev { y - y_prev } else { y_prev - y };
+        if diff <= U256::from(1) {
+            return Some(y);
+        }
+    }
+
+    None
+}
+
+/// Fixed block window per `eth_getLogs` page in `index_from_logs` — large
+/// enough to keep a full backfill's round-trip count small, small enough to
+/// stay under a typical provider's log-count/response-size limits.
+const INDEX_WINDOW_BLOCKS: u64 = 2_000;
+/// Attempts per window before `index_from_logs` gives up on the backfill
+/// rather than silently skipping a gap in the scanned range.
+const INDEX_WINDOW_RETRIES: u32 = 3;
+const INDEX_RETRY_DELAY: Duration = Duration::from_secs(2);
+
 /// Pool discovery and management
 pub struct PoolManager {
     provider: Arc<Provider<Http>>,
     pools: RwLock<HashMap<Address, Pool>>,
-    
+
     // Factory addresses
     uniswap_v3_factory: Address,
     sushi_factory: Address,
     camelot_factory: Address,
+
+    // Canonical Uniswap V3 Quoter (same address across the chains Uniswap
+    // has deployed it, including Arbitrum), used by `quote_exact_in`'s
+    // simulated path for `PoolType::UniswapV3`.
+    uniswap_v3_quoter: Address,
+
+    /// Config flag gating `quote_exact_in`'s simulated path. Off by default:
+    /// each simulated quote forks state and runs a real EVM call, so it's
+    /// meaningfully slower than the analytic formula and only worth paying
+    /// for when exactness matters more than throughput.
+    simulate_quotes: bool,
+
+    /// Canonical Multicall3 contract — deployed at the same address across
+    /// virtually every EVM chain, including Arbitrum. `call_batch` aggregates
+    /// discovery/refresh reads into `aggregate3` calls against this.
+    multicall3: Address,
+
+    /// Reads packed into a single `aggregate3` call. Kept well under typical
+    /// RPC gas/response limits; `call_chunk`'s latency log is what you'd
+    /// watch to tune this down for a slower provider.
+    multicall_chunk_size: usize,
+
+    /// Per-batch timeout. A batch that blows through this is retried once,
+    /// independently of every other batch, so one non-responsive chunk
+    /// doesn't stall the rest of a `refresh_all` pass.
+    multicall_timeout: Duration,
+
+    /// Last block `index_from_logs` fully scanned, so a later backfill call
+    /// can resume from here instead of rescanning from genesis.
+    last_indexed_block: RwLock<Option<u64>>,
 }
 
 impl PoolManager {
     pub fn new(provider: Arc<Provider<Http>>) -> Self {
         use std::str::FromStr;
-        
+
         Self {
             provider,
             pools: RwLock::new(HashMap::new()),
             uniswap_v3_factory: Address::from_str("0x1F98431c8aD98523631AE4a59f267346ea31F984").unwrap(),
             sushi_factory: Address::from_str("0xc35DADB65012eC5796536bD9864eD8773aBc74C4").unwrap(),
             camelot_factory: Address::from_str("0x6EcCab422D763aC031210895C81787E87B43A652").unwrap(),
+            uniswap_v3_quoter: Address::from_str("0xb27308f9F90D607463bb33eA1BeBb41C27CE5AB6").unwrap(),
+            simulate_quotes: false,
+            multicall3: Address::from_str("0xcA11bde05977b3631167028862bE2a173976CA11").unwrap(),
+            multicall_chunk_size: 200,
+            multicall_timeout: Duration::from_secs(5),
+            last_indexed_block: RwLock::new(None),
         }
     }
-    
-    /// Discover all pools for a token pair
+
+    /// Enable/disable `quote_exact_in`'s simulated (forked-EVM) quoting
+    /// path. Analytic quoting always remains available as the fast path and
+    /// as a fallback if simulation fails.
+    pub fn set_simulate_quotes(&mut self, enabled: bool) {
+        self.simulate_quotes = enabled;
+    }
+
+    /// Tune how many reads `call_batch` packs into a single `aggregate3`
+    /// call. Lower this if a provider's response/gas limits make
+    /// `call_chunk`'s latency log spike at the default size.
+    pub fn set_multicall_chunk_size(&mut self, size: usize) {
+        self.multicall_chunk_size = size.max(1);
+    }
+
+    /// Tune how long a single `aggregate3` batch gets before it's retried
+    /// independently rather than left to stall the rest of a pass.
+    pub fn set_multicall_timeout(&mut self, timeout: Duration) {
+        self.multicall_timeout = timeout;
+    }
+
+    /// Discover all pools for a token pair. Every `getPool`/`getPair` lookup
+    /// and every per-pool detail read (`token0`/`token1`/`liquidity`/`slot0`
+    /// for V3, `getReserves` for V2) is packed into a couple of `aggregate3`
+    /// batches via `call_batch`, rather than one RPC round-trip per call —
+    /// see `call_batch` for why.
     pub async fn discover_pools(&self, token_a: Address, token_b: Address) -> Vec<Pool> {
-        let mut pools = Vec::new();
-        
-        // Sort tokens
         let (token0, token1) = if token_a < token_b {
             (token_a, token_b)
         } else {
             (token_b, token_a)
         };
-        
-        // Find Uniswap V3 pools (all fee tiers)
-        let v3_factory = UniswapV3Factory::new(self.uniswap_v3_factory, self.provider.clone());
-        for fee in [100u32, 500, 3000, 10000] {
-            if let Ok(pool_addr) = v3_factory.get_pool(token0, token1, fee.into()).call().await {
-                if pool_addr != Address::zero() {
-                    if let Some(pool) = self.fetch_v3_pool(pool_addr, fee).await {
-                        pools.push(pool);
-                    }
-                }
-            }
+
+        let fee_tiers = [100u32, 500, 3000, 10000];
+        let mut lookup_calls = Vec::with_capacity(fee_tiers.len() + 2);
+        for fee in fee_tiers {
+            lookup_calls.push((self.uniswap_v3_factory, encode_get_pool(token0, token1, fee)));
         }
-        
-        // Find SushiSwap pool
-        if let Some(pool) = self.fetch_v2_pool(self.sushi_factory, token0, token1, PoolType::SushiSwap).await {
-            pools.push(pool);
+        lookup_calls.push((self.sushi_factory, encode_get_pair(token0, token1)));
+        lookup_calls.push((self.camelot_factory, encode_get_pair(token0, token1)));
+
+        let lookup_results = self.call_batch(lookup_calls).await;
+        let (v3_lookups, v2_lookups) = lookup_results.split_at(fee_tiers.len());
+
+        let v3_pool_addrs: Vec<(u32, Address)> = fee_tiers
+            .iter()
+            .zip(v3_lookups.iter())
+            .filter_map(|(fee, result)| {
+                let addr = decode_address(result.as_deref()?)?;
+                (addr != Address::zero()).then_some((*fee, addr))
+            })
+            .collect();
+
+        let v2_pairs: Vec<(PoolType, Address)> = [
+            (PoolType::SushiSwap, &v2_lookups[0]),
+            (PoolType::Camelot, &v2_lookups[1]),
+        ]
+        .into_iter()
+        .filter_map(|(pool_type, result)| {
+            let addr = decode_address(result.as_deref()?)?;
+            (addr != Address::zero()).then_some((pool_type, addr))
+        })
+        .collect();
+
+        let mut detail_calls = Vec::with_capacity(v3_pool_addrs.len() * 4 + v2_pairs.len());
+        for (_, addr) in &v3_pool_addrs {
+            detail_calls.push((*addr, encode_token0()));
+            detail_calls.push((*addr, encode_token1()));
+            detail_calls.push((*addr, encode_liquidity()));
+            detail_calls.push((*addr, encode_slot0()));
         }
-        
-        // Find Camelot pool
-        if let Some(pool) = self.fetch_v2_pool(self.camelot_factory, token0, token1, PoolType::Camelot).await {
-            pools.push(pool);
+        for (_, addr) in &v2_pairs {
+            detail_calls.push((*addr, encode_get_reserves()));
         }
-        
-        // Store pools
+
+        let detail_results = self.call_batch(detail_calls).await;
+        let mut cursor = 0;
+        let mut pools = Vec::new();
+
+        for (fee, addr) in &v3_pool_addrs {
+            let slice = &detail_results[cursor..cursor + 4];
+            cursor += 4;
+            if let Some(pool) = decode_v3_pool(*addr, *fee, slice) {
+                pools.push(pool);
+            }
+        }
+        for (pool_type, addr) in &v2_pairs {
+            let slice = &detail_results[cursor..cursor + 1];
+            cursor += 1;
+            if let Some(pool) = decode_v2_pool(*addr, token0, token1, pool_type.clone(), &slice[0]) {
+                pools.push(pool);
+            }
+        }
+
         let mut stored = self.pools.write().await;
         for pool in &pools {
             stored.insert(pool.address, pool.clone());
         }
-        
+
         pools
     }
-    
-    async fn fetch_v3_pool(&self, pool_addr: Address, fee: u32) -> Option<Pool> {
-        let pool = UniswapV3Pool::new(pool_addr, self.provider.clone());
-        
-        let token0 = pool.token_0().call().await.ok()?;
-        let token1 = pool.token_1().call().await.ok()?;
-        let liquidity = pool.liquidity().call().await.ok()?;
-        let slot0 = pool.slot_0().call().await.ok()?;
-        
-        // Skip pools with no liquidity
-        if liquidity == 0 {
-            return None;
-        }
-        
-        // Convert sqrtPriceX96 to virtual reserves for V3
-        // sqrtPriceX96 = sqrt(price) * 2^96
-        // price = token1/token0 = reserve1/reserve0
-        // We use virtual reserves based on liquidity and current price
-        let sqrt_price_x96 = U256::from(slot0.0);
-        let q96 = U256::from(1u128) << 96;
-        
-        // Virtual reserves at current tick:
-        // reserve0 = L / sqrt(P)
-        // reserve1 = L * sqrt(P)
-        // Using fixed point math: L * 2^96 / sqrtPriceX96 and L * sqrtPriceX96 / 2^96
-        let liq = U256::from(liquidity);
-        
-        // Prevent division by zero
-        if sqrt_price_x96.is_zero() {
-            return None;
-        }
-        
-        let reserve0 = (liq * q96) / sqrt_price_x96;
-        let reserve1 = (liq * sqrt_price_x96) / q96;
-        
-        // Skip if reserves too small
-        if reserve0 < U256::from(1000u64) || reserve1 < U256::from(1000u64) {
-            return None;
+
+    /// Backfills the pool map from `PoolCreated`/`PairCreated` logs over
+    /// `[from_block, to_block]`, so a newly listed or long-tail pool — never
+    /// in `get_top_arbitrum_tokens`'s allow-list, but often the least
+    /// efficient market and best arb target — still ends up tracked. Pages
+    /// `eth_getLogs` in fixed `INDEX_WINDOW_BLOCKS`-sized windows with
+    /// retry; discovered pools are stored with zero reserves and only
+    /// become quotable once `refresh_pool`/`refresh_all` has run on them.
+    /// Each window that scans cleanly advances `last_indexed_block`, so a
+    /// caller can resume a later backfill from `last_indexed_block().await`
+    /// instead of rescanning from genesis; a window that exhausts its
+    /// retries stops the scan there rather than silently skipping ahead.
+    pub async fn index_from_logs(&self, from_block: u64, to_block: u64) -> anyhow::Result<usize> {
+        let mut discovered = 0usize;
+        let mut window_start = from_block;
+
+        while window_start <= to_block {
+            let window_end = (window_start + INDEX_WINDOW_BLOCKS - 1).min(to_block);
+
+            let v3_logs = self.get_logs_with_retry(self.uniswap_v3_factory, pool_created_topic(), window_start, window_end).await?;
+            let sushi_logs = self.get_logs_with_retry(self.sushi_factory, pair_created_topic(), window_start, window_end).await?;
+            let camelot_logs = self.get_logs_with_retry(self.camelot_factory, pair_created_topic(), window_start, window_end).await?;
+
+            let mut new_pools: Vec<Pool> = v3_logs.iter().filter_map(decode_pool_created).collect();
+            new_pools.extend(sushi_logs.iter().filter_map(|log| decode_pair_created(log, PoolType::SushiSwap)));
+            new_pools.extend(camelot_logs.iter().filter_map(|log| decode_pair_created(log, PoolType::Camelot)));
+
+            if !new_pools.is_empty() {
+                let mut stored = self.pools.write().await;
+                for pool in new_pools {
+                    // A pool already tracked (e.g. found via `discover_pools`
+                    // and refreshed since) keeps its real reserves rather
+                    // than being clobbered by this pass's zero-reserve stub.
+                    if let std::collections::hash_map::Entry::Vacant(entry) = stored.entry(pool.address) {
+                        discovered += 1;
+                        entry.insert(pool);
+                    }
+                }
+            }
+
+            *self.last_indexed_block.write().await = Some(window_end);
+            window_start = window_end + 1;
         }
-        
-        Some(Pool {
-            address: pool_addr,
-            token0,
-            token1,
-            pool_type: PoolType::UniswapV3 { fee },
-            reserve0,
-            reserve1,
-            liquidity: liq,
-            fee_bps: fee / 100, // Convert from 1/1000000 to bps
-        })
+
+        Ok(discovered)
     }
-    
-    async fn fetch_v2_pool(
+
+    /// Last block `index_from_logs` fully scanned — see its doc comment.
+    pub async fn last_indexed_block(&self) -> Option<u64> {
+        *self.last_indexed_block.read().await
+    }
+
+    /// Runs one `eth_getLogs` window, making up to `INDEX_WINDOW_RETRIES`
+    /// attempts with a fixed delay between them before giving up on the
+    /// whole backfill —
+    /// a window this can't eventually read isn't safe to skip over, since
+    /// that would silently leave a gap in the scanned range.
+    async fn get_logs_with_retry(
         &self,
         factory: Address,
-        token0: Address,
-        token1: Address,
-        pool_type: PoolType,
-    ) -> Option<Pool> {
-        let factory_contract = UniswapV2Factory::new(factory, self.provider.clone());
-        let pair_addr = factory_contract.get_pair(token0, token1).call().await.ok()?;
-        
-        if pair_addr == Address::zero() {
-            return None;
+        topic0: H256,
+        from_block: u64,
+        to_block: u64,
+    ) -> anyhow::Result<Vec<Log>> {
+        let filter = Filter::new()
+            .address(factory)
+            .topic0(topic0)
+            .from_block(from_block)
+            .to_block(to_block);
+
+        let mut last_err = None;
+        for attempt in 1..=INDEX_WINDOW_RETRIES {
+            match self.provider.get_logs(&filter).await {
+                Ok(logs) => return Ok(logs),
+                Err(e) => {
+                    warn!(
+                        "eth_getLogs for {:?} blocks {}..={} failed (attempt {}): {}",
+                        factory, from_block, to_block, attempt, e
+                    );
+                    last_err = Some(e);
+                    if attempt < INDEX_WINDOW_RETRIES {
+                        tokio::time::sleep(INDEX_RETRY_DELAY).await;
+                    }
+                }
+            }
         }
-        
-        let pair = UniswapV2Pair::new(pair_addr, self.provider.clone());
-        let reserves = pair.get_reserves().call().await.ok()?;
-        
-        Some(Pool {
-            address: pair_addr,
-            token0,
-            token1,
-            pool_type,
-            reserve0: U256::from(reserves.0),
-            reserve1: U256::from(reserves.1),
-            liquidity: U256::from(reserves.0) + U256::from(reserves.1),
-            fee_bps: 30, // 0.30% for V2
-        })
+
+        anyhow::bail!(
+            "eth_getLogs for {:?} blocks {}..={} failed after {} attempts: {}",
+            factory, from_block, to_block, INDEX_WINDOW_RETRIES, last_err.unwrap()
+        )
     }
-    
-    /// Update reserves for all stored pools - PARALLELIZED
+
+    /// Update reserves for all stored pools, batched through
+    /// `call_batch` instead of one future per pool — see `call_batch`.
     pub async fn refresh_all(&self) {
-        use futures::future::join_all;
-        
         let pools = self.pools.read().await;
         let pool_info: Vec<(Address, Pool)> = pools.iter()
             .map(|(addr, pool)| (*addr, pool.clone()))
             .collect();
         drop(pools);
-        
-        // Create parallel futures for each pool
-        let futures: Vec<_> = pool_info.iter().map(|(addr, pool)| {
-            let pool_addr = *addr;
-            let pool = pool.clone();
-            let provider = self.provider.clone();
-            
-            async move {
-                let updated = match &pool.pool_type {
-                    PoolType::UniswapV3 { fee: _ } => {
-                        let v3_pool = UniswapV3Pool::new(pool_addr, provider.clone());
-                        
-                        // Fetch liquidity and slot0 sequentially (but many pools in parallel)
-                        let liquidity = match v3_pool.liquidity().call().await {
-                            Ok(l) => l,
-                            Err(_) => return (pool_addr, None),
-                        };
-                        
-                        if liquidity == 0 {
-                            return (pool_addr, None);
-                        }
-                        
-                        let slot0 = match v3_pool.slot_0().call().await {
-                            Ok(s) => s,
-                            Err(_) => return (pool_addr, None),
-                        };
-                        
-                        let sqrt_price_x96 = U256::from(slot0.0);
-                        let q96 = U256::from(1u128) << 96;
-                        let liq = U256::from(liquidity);
-                        
-                        if sqrt_price_x96.is_zero() {
-                            return (pool_addr, None);
-                        }
-                        
-                        let reserve0 = (liq * q96) / sqrt_price_x96;
-                        let reserve1 = (liq * sqrt_price_x96) / q96;
-                        
-                        if reserve0 >= U256::from(1000u64) && reserve1 >= U256::from(1000u64) {
-                            Some(Pool {
-                                reserve0,
-                                reserve1,
-                                liquidity: liq,
-                                ..pool
-                            })
-                        } else {
-                            None
-                        }
+
+        let mut calls = Vec::new();
+        for (addr, pool) in &pool_info {
+            match &pool.pool_type {
+                PoolType::UniswapV3 { .. } => {
+                    calls.push((*addr, encode_liquidity()));
+                    calls.push((*addr, encode_slot0()));
+                }
+                PoolType::SushiSwap | PoolType::Camelot => {
+                    calls.push((*addr, encode_get_reserves()));
+                }
+                // StableSwap/LSD pools aren't discovered through a factory
+                // yet, so there's no refresh path for them (see
+                // `discover_pools`). LSD target rates are refreshed out of
+                // band via `set_target_rate`.
+                PoolType::StableSwap { .. } | PoolType::LsdStable { .. } => {}
+            }
+        }
+
+        let results = self.call_batch(calls).await;
+        let mut cursor = 0;
+        let mut updated_pools = Vec::new();
+
+        for (addr, pool) in &pool_info {
+            match &pool.pool_type {
+                PoolType::UniswapV3 { .. } => {
+                    let slice = &results[cursor..cursor + 2];
+                    cursor += 2;
+                    if let Some((reserve0, reserve1, liquidity)) = decode_v3_update(slice) {
+                        updated_pools.push((*addr, Pool { reserve0, reserve1, liquidity, ..pool.clone() }));
                     }
-                    PoolType::SushiSwap | PoolType::Camelot => {
-                        let pair = UniswapV2Pair::new(pool_addr, provider);
-                        if let Ok(reserves) = pair.get_reserves().call().await {
-                            Some(Pool {
-                                reserve0: U256::from(reserves.0),
-                                reserve1: U256::from(reserves.1),
-                                liquidity: U256::from(reserves.0) + U256::from(reserves.1),
-                                ..pool
-                            })
-                        } else {
-                            None
-                        }
+                }
+                PoolType::SushiSwap | PoolType::Camelot => {
+                    let slice = &results[cursor..cursor + 1];
+                    cursor += 1;
+                    if let Some((reserve0, reserve1)) = decode_reserves(slice[0].as_deref()) {
+                        updated_pools.push((*addr, Pool {
+                            reserve0,
+                            reserve1,
+                            liquidity: reserve0 + reserve1,
+                            ..pool.clone()
+                        }));
                     }
-                };
-                
-                (pool_addr, updated)
+                }
+                PoolType::StableSwap { .. } | PoolType::LsdStable { .. } => {}
             }
-        }).collect();
-        
-        // Execute all in parallel
-        let results = join_all(futures).await;
-        
-        // Update pools
+        }
+
         let mut pools = self.pools.write().await;
-        for (addr, updated) in results {
-            if let Some(p) = updated {
-                pools.insert(addr, p);
-            }
+        for (addr, updated) in updated_pools {
+            pools.insert(addr, updated);
         }
     }
-    
-    /// Update reserves for a single pool
+
+    /// Update reserves for a single pool, through the same `call_batch`
+    /// path `refresh_all` uses (a one-chunk batch), so a stalled provider
+    /// times out the same way here as it would in a full pass.
     pub async fn refresh_pool(&self, pool_addr: Address) {
         let pools = self.pools.read().await;
         let pool = match pools.get(&pool_addr) {
@@ -347,32 +708,111 @@ impl PoolManager {
             None => return,
         };
         drop(pools);
-        
+
         let updated = match &pool.pool_type {
-            PoolType::UniswapV3 { fee } => {
-                self.fetch_v3_pool(pool_addr, *fee).await
+            PoolType::UniswapV3 { .. } => {
+                let results = self.call_batch(vec![
+                    (pool_addr, encode_liquidity()),
+                    (pool_addr, encode_slot0()),
+                ]).await;
+                decode_v3_update(&results).map(|(reserve0, reserve1, liquidity)| Pool {
+                    reserve0,
+                    reserve1,
+                    liquidity,
+                    ..pool
+                })
             }
             PoolType::SushiSwap | PoolType::Camelot => {
-                let pair = UniswapV2Pair::new(pool_addr, self.provider.clone());
-                if let Ok(reserves) = pair.get_reserves().call().await {
-                    Some(Pool {
-                        reserve0: U256::from(reserves.0),
-                        reserve1: U256::from(reserves.1),
-                        liquidity: U256::from(reserves.0) + U256::from(reserves.1),
-                        ..pool
-                    })
-                } else {
-                    None
-                }
+                let results = self.call_batch(vec![(pool_addr, encode_get_reserves())]).await;
+                decode_reserves(results[0].as_deref()).map(|(reserve0, reserve1)| Pool {
+                    reserve0,
+                    reserve1,
+                    liquidity: reserve0 + reserve1,
+                    ..pool
+                })
             }
+            // StableSwap/LSD pools aren't discovered through a factory yet,
+            // so there's no refresh path for them (see `discover_pools`). LSD
+            // target rates are refreshed out of band via `set_target_rate`.
+            PoolType::StableSwap { .. } | PoolType::LsdStable { .. } => None,
         };
-        
+
         if let Some(p) = updated {
             let mut pools = self.pools.write().await;
             pools.insert(pool_addr, p);
         }
     }
-    
+
+    /// Aggregates `calls` into `aggregate3` batches of at most
+    /// `multicall_chunk_size`, so a discovery/refresh pass that would
+    /// otherwise be thousands of independent RPC round-trips becomes a
+    /// handful — roughly `calls.len() / multicall_chunk_size`. Each result
+    /// is `None` for a call the target reverted on (expected for a
+    /// nonexistent pool/pair) or that didn't come back within
+    /// `multicall_timeout` even after a retry.
+    async fn call_batch(&self, calls: Vec<(Address, Vec<u8>)>) -> Vec<Option<Vec<u8>>> {
+        let mut results = Vec::with_capacity(calls.len());
+        for chunk in calls.chunks(self.multicall_chunk_size) {
+            results.extend(self.call_chunk(chunk).await);
+        }
+        results
+    }
+
+    /// Runs one `aggregate3` batch under `multicall_timeout`, retrying once
+    /// independently of every other chunk so a single non-responsive batch
+    /// doesn't stall the rest of the pass. Falls back to all-`None` for the
+    /// chunk if both attempts fail.
+    async fn call_chunk(&self, chunk: &[(Address, Vec<u8>)]) -> Vec<Option<Vec<u8>>> {
+        for attempt in 1..=2 {
+            let started = Instant::now();
+            match tokio::time::timeout(self.multicall_timeout, self.aggregate3(chunk)).await {
+                Ok(Ok(results)) if results.len() == chunk.len() => {
+                    debug!("multicall batch of {} calls took {:?}", chunk.len(), started.elapsed());
+                    return results;
+                }
+                Ok(Ok(results)) => {
+                    warn!(
+                        "multicall batch of {} calls returned {} results (attempt {})",
+                        chunk.len(), results.len(), attempt
+                    );
+                }
+                Ok(Err(e)) => {
+                    warn!("multicall batch of {} calls failed (attempt {}): {}", chunk.len(), attempt, e);
+                }
+                Err(_) => {
+                    warn!(
+                        "multicall batch of {} calls timed out after {:?} (attempt {})",
+                        chunk.len(), self.multicall_timeout, attempt
+                    );
+                }
+            }
+        }
+        vec![None; chunk.len()]
+    }
+
+    async fn aggregate3(&self, chunk: &[(Address, Vec<u8>)]) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        let calldata = encode_aggregate3(chunk);
+        let tx = TransactionRequest::new().to(self.multicall3).data(calldata);
+        let output = self.provider.call(&tx.into(), None).await?;
+        Ok(decode_aggregate3_result(&output))
+    }
+
+    /// Update a tracked LSD pool's target exchange rate (polled externally
+    /// from the derivative's rate provider). Replaces the stored `Pool` so
+    /// any snapshot a caller is still holding from `get_pool`/`get_pools`
+    /// is stale rather than silently wrong. Returns `false` if the pool
+    /// isn't tracked or isn't an `LsdStable` pool.
+    pub async fn set_target_rate(&self, pool_addr: Address, target_rate_bps: u64) -> bool {
+        let mut pools = self.pools.write().await;
+        let Some(pool) = pools.get(&pool_addr) else { return false };
+        let PoolType::LsdStable { amp, rate_token, .. } = pool.pool_type else { return false };
+
+        let mut updated = pool.clone();
+        updated.pool_type = PoolType::LsdStable { amp, target_rate_bps, rate_token };
+        pools.insert(pool_addr, updated);
+        true
+    }
+
     /// Get all pools for a token pair
     pub async fn get_pools(&self, token0: Address, token1: Address) -> Vec<Pool> {
         let pools = self.pools.read().await;
@@ -384,6 +824,356 @@ impl PoolManager {
             .cloned()
             .collect()
     }
+
+    /// Look up one pool by its address.
+    pub async fn get_pool(&self, address: Address) -> Option<Pool> {
+        self.pools.read().await.get(&address).cloned()
+    }
+
+    /// Every pool currently known, across every token pair — the trading-pair
+    /// enumeration `ArbitrageDetector::find_cycles` builds its graph from.
+    pub async fn all_pools(&self) -> Vec<Pool> {
+        self.pools.read().await.values().cloned().collect()
+    }
+
+    /// Quote `amount_in` of `token_in` through `pool`, dispatching to the
+    /// exact-but-slow simulated path when `simulate_quotes` is on and the
+    /// pool has one, otherwise the analytic formula
+    /// (`Pool::get_amount_out`). Falls back to analytic if simulation fails
+    /// (e.g. the RPC round-trip errors, or the quoter call reverts) so a
+    /// transient fork-state issue doesn't lose a quote outright.
+    pub async fn quote_exact_in(&self, pool: &Pool, token_in: Address, amount_in: U256) -> U256 {
+        if self.simulate_quotes {
+            if let Some((to, calldata)) = self.build_quote_call(pool, token_in, amount_in) {
+                match call_view(self.provider.clone(), None, to, calldata) {
+                    Ok(output) if output.len() >= 32 => {
+                        return U256::from_big_endian(&output[0..32]);
+                    }
+                    Ok(_) => {
+                        warn!("simulated quote for pool {:?} returned a short result; falling back to analytic", pool.address);
+                    }
+                    Err(e) => {
+                        warn!("simulated quote for pool {:?} failed: {}; falling back to analytic", pool.address, e);
+                    }
+                }
+            }
+        }
+
+        pool.get_amount_out(amount_in, token_in)
+    }
+
+    /// Builds `(target, calldata)` for the pool's quoter/view-function call,
+    /// if `pool.pool_type` has one. Returns `None` for pool types whose
+    /// analytic formula already matches their real contract exactly
+    /// (`SushiSwap`, `StableSwap`, `LsdStable`), so there's nothing to gain
+    /// from simulating them.
+    fn build_quote_call(&self, pool: &Pool, token_in: Address, amount_in: U256) -> Option<(Address, Vec<u8>)> {
+        match pool.pool_type {
+            PoolType::UniswapV3 { fee } => {
+                let token_out = if token_in == pool.token0 { pool.token1 } else { pool.token0 };
+                Some((self.uniswap_v3_quoter, encode_quote_exact_input_single(token_in, token_out, fee, amount_in)))
+            }
+            PoolType::Camelot => Some((pool.address, encode_get_amount_out(amount_in, token_in))),
+            PoolType::SushiSwap | PoolType::StableSwap { .. } | PoolType::LsdStable { .. } => None,
+        }
+    }
+}
+
+/// `Quoter.quoteExactInputSingle(address,address,uint24,uint256,uint160)`
+/// (selector `0xf7729d43`), with `sqrtPriceLimitX96 = 0` for "no limit".
+fn encode_quote_exact_input_single(token_in: Address, token_out: Address, fee: u32, amount_in: U256) -> Vec<u8> {
+    let mut data = vec![0xf7, 0x72, 0x9d, 0x43];
+    data.extend_from_slice(&address_word(token_in));
+    data.extend_from_slice(&address_word(token_out));
+    data.extend_from_slice(&u256_word(U256::from(fee)));
+    data.extend_from_slice(&u256_word(amount_in));
+    data.extend_from_slice(&[0u8; 32]); // sqrtPriceLimitX96 = 0
+    data
+}
+
+/// Camelot pair's `getAmountOut(uint256 amountIn, address tokenIn)`
+/// (selector `0xf140a35a`) — Camelot's own view function, so its dynamic
+/// directional fee is applied exactly rather than approximated.
+fn encode_get_amount_out(amount_in: U256, token_in: Address) -> Vec<u8> {
+    let mut data = vec![0xf1, 0x40, 0xa3, 0x5a];
+    data.extend_from_slice(&u256_word(amount_in));
+    data.extend_from_slice(&address_word(token_in));
+    data
+}
+
+fn u256_word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn address_word(addr: Address) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..32].copy_from_slice(addr.as_bytes());
+    word
+}
+
+/// `Factory.getPool(address,address,uint24)` (selector `0x1698ee82`).
+fn encode_get_pool(token_a: Address, token_b: Address, fee: u32) -> Vec<u8> {
+    let mut data = vec![0x16, 0x98, 0xee, 0x82];
+    data.extend_from_slice(&address_word(token_a));
+    data.extend_from_slice(&address_word(token_b));
+    data.extend_from_slice(&u256_word(U256::from(fee)));
+    data
+}
+
+/// `Factory.getPair(address,address)` (selector `0xe6a43905`).
+fn encode_get_pair(token_a: Address, token_b: Address) -> Vec<u8> {
+    let mut data = vec![0xe6, 0xa4, 0x39, 0x05];
+    data.extend_from_slice(&address_word(token_a));
+    data.extend_from_slice(&address_word(token_b));
+    data
+}
+
+/// `Pool.token0()` (selector `0x0dfe1681`).
+fn encode_token0() -> Vec<u8> {
+    vec![0x0d, 0xfe, 0x16, 0x81]
+}
+
+/// `Pool.token1()` (selector `0xd21220a7`).
+fn encode_token1() -> Vec<u8> {
+    vec![0xd2, 0x12, 0x20, 0xa7]
+}
+
+/// `Pool.liquidity()` (selector `0x1a686502`).
+fn encode_liquidity() -> Vec<u8> {
+    vec![0x1a, 0x68, 0x65, 0x02]
+}
+
+/// `Pool.slot0()` (selector `0x3850c7bd`).
+fn encode_slot0() -> Vec<u8> {
+    vec![0x38, 0x50, 0xc7, 0xbd]
+}
+
+/// `Pair.getReserves()` (selector `0x0902f1ac`).
+fn encode_get_reserves() -> Vec<u8> {
+    vec![0x09, 0x02, 0xf1, 0xac]
+}
+
+/// `Multicall3.aggregate3((address,bool,bytes)[])` (selector `0x82ad56cb`).
+/// `allowFailure` is always true — a reverting per-call read (no pool at
+/// this fee tier, a pair that doesn't exist) is an expected per-call
+/// outcome, not a reason to fail the whole batch.
+fn encode_aggregate3(calls: &[(Address, Vec<u8>)]) -> Vec<u8> {
+    let tokens = Token::Array(
+        calls
+            .iter()
+            .map(|(target, data)| {
+                Token::Tuple(vec![
+                    Token::Address(*target),
+                    Token::Bool(true),
+                    Token::Bytes(data.clone()),
+                ])
+            })
+            .collect(),
+    );
+    let mut data = vec![0x82, 0xad, 0x56, 0xcb];
+    data.extend(encode(&[tokens]));
+    data
+}
+
+/// Decodes `aggregate3`'s `(bool success, bytes returnData)[]` return value,
+/// one entry per call in the batch that produced it, in the same order.
+/// A call whose `success` came back `false` decodes to `None` here.
+fn decode_aggregate3_result(output: &[u8]) -> Vec<Option<Vec<u8>>> {
+    let param = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes])));
+    let Ok(tokens) = decode(&[param], output) else { return Vec::new() };
+    let Some(Token::Array(results)) = tokens.into_iter().next() else { return Vec::new() };
+
+    results
+        .into_iter()
+        .map(|result| {
+            let Token::Tuple(mut fields) = result else { return None };
+            let return_data = fields.pop()?;
+            let success = fields.pop()?;
+            if !matches!(success, Token::Bool(true)) {
+                return None;
+            }
+            match return_data {
+                Token::Bytes(bytes) => Some(bytes),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The last 20 bytes of a 32-byte ABI word, as returned by `getPool`/
+/// `getPair`/`token0`/`token1`. `None` for a short/empty (reverted) result.
+fn decode_address(word: &[u8]) -> Option<Address> {
+    (word.len() >= 32).then(|| Address::from_slice(&word[12..32]))
+}
+
+/// V3's sqrtPriceX96 → virtual-reserve conversion
+/// (`reserve0 = L * 2^96 / sqrtPriceX96`, `reserve1 = L * sqrtPriceX96 / 2^96`),
+/// shared by the discovery and refresh paths so the formula can't drift
+/// between them. Returns `None` for a zero price or reserves too small to
+/// bother tracking.
+fn virtual_reserves(liquidity: U256, sqrt_price_x96: U256) -> Option<(U256, U256)> {
+    if liquidity.is_zero() || sqrt_price_x96.is_zero() {
+        return None;
+    }
+
+    let q96 = U256::from(1u128) << 96;
+    let reserve0 = (liquidity * q96) / sqrt_price_x96;
+    let reserve1 = (liquidity * sqrt_price_x96) / q96;
+
+    if reserve0 < U256::from(1000u64) || reserve1 < U256::from(1000u64) {
+        return None;
+    }
+
+    Some((reserve0, reserve1))
+}
+
+/// Builds a `Pool` for a newly-discovered V3 pool from its batched detail
+/// reads — `results` is `[token0, token1, liquidity, slot0]`, in the order
+/// `discover_pools` queued them.
+fn decode_v3_pool(pool_addr: Address, fee: u32, results: &[Option<Vec<u8>>]) -> Option<Pool> {
+    let token0 = decode_address(results[0].as_deref()?)?;
+    let token1 = decode_address(results[1].as_deref()?)?;
+    let liquidity = U256::from_big_endian(results[2].as_deref()?);
+    let slot0 = results[3].as_deref()?;
+    if slot0.len() < 32 {
+        return None;
+    }
+    let sqrt_price_x96 = U256::from_big_endian(&slot0[0..32]);
+
+    let (reserve0, reserve1) = virtual_reserves(liquidity, sqrt_price_x96)?;
+
+    Some(Pool {
+        address: pool_addr,
+        token0,
+        token1,
+        pool_type: PoolType::UniswapV3 { fee },
+        reserve0,
+        reserve1,
+        liquidity,
+        fee_bps: fee / 100,
+        quote_mode: QuoteMode::for_pool_type(&PoolType::UniswapV3 { fee }),
+    })
+}
+
+/// Builds a `Pool` for a newly-discovered V2-style pair from its batched
+/// `getReserves` read.
+fn decode_v2_pool(
+    pair_addr: Address,
+    token0: Address,
+    token1: Address,
+    pool_type: PoolType,
+    reserves_result: &Option<Vec<u8>>,
+) -> Option<Pool> {
+    let (reserve0, reserve1) = decode_reserves(reserves_result.as_deref())?;
+
+    Some(Pool {
+        address: pair_addr,
+        token0,
+        token1,
+        quote_mode: QuoteMode::for_pool_type(&pool_type),
+        pool_type,
+        reserve0,
+        reserve1,
+        liquidity: reserve0 + reserve1,
+        fee_bps: 30,
+    })
+}
+
+/// Decodes a `getReserves()` result's leading `(uint112 reserve0, uint112
+/// reserve1, ...)` words. `None` for a short/reverted result.
+fn decode_reserves(result: Option<&[u8]>) -> Option<(U256, U256)> {
+    let bytes = result?;
+    if bytes.len() < 64 {
+        return None;
+    }
+    Some((U256::from_big_endian(&bytes[0..32]), U256::from_big_endian(&bytes[32..64])))
+}
+
+/// Reconstructs `[reserve0, reserve1, liquidity]` for an existing V3 pool
+/// from its batched `[liquidity, slot0]` refresh reads.
+fn decode_v3_update(results: &[Option<Vec<u8>>]) -> Option<(U256, U256, U256)> {
+    let liquidity = U256::from_big_endian(results[0].as_deref()?);
+    let slot0 = results[1].as_deref()?;
+    if slot0.len() < 32 {
+        return None;
+    }
+    let sqrt_price_x96 = U256::from_big_endian(&slot0[0..32]);
+    let (reserve0, reserve1) = virtual_reserves(liquidity, sqrt_price_x96)?;
+    Some((reserve0, reserve1, liquidity))
+}
+
+/// `PoolCreated(address,address,uint24,int24,address)` topic0 — the V3
+/// factory's pool-creation event, scanned by `index_from_logs`.
+fn pool_created_topic() -> H256 {
+    H256::from_slice(&[
+        0x78, 0x3c, 0xca, 0x1c, 0x04, 0x12, 0xdd, 0x0d, 0x69, 0x5e, 0x78, 0x45, 0x68, 0xc9, 0x6d, 0xa2,
+        0xe9, 0xc2, 0x2f, 0xf9, 0x89, 0x35, 0x7a, 0x2e, 0x8b, 0x1d, 0x9b, 0x2b, 0x4e, 0x6b, 0x71, 0x18,
+    ])
+}
+
+/// `PairCreated(address,address,address,uint256)` topic0 — the V2-style
+/// factories' (Sushi, Camelot) pair-creation event.
+fn pair_created_topic() -> H256 {
+    H256::from_slice(&[
+        0x0d, 0x36, 0x48, 0xbd, 0x0f, 0x6b, 0xa8, 0x01, 0x34, 0xa3, 0x3b, 0xa9, 0x27, 0x5a, 0xc5, 0x85,
+        0xd9, 0xd3, 0x15, 0xf0, 0xad, 0x83, 0x55, 0xcd, 0xde, 0xfd, 0xe3, 0x1a, 0xfa, 0x28, 0xd0, 0xe9,
+    ])
+}
+
+/// Decodes a V3 factory's `PoolCreated(address indexed token0, address
+/// indexed token1, uint24 indexed fee, int24 tickSpacing, address pool)`
+/// log into a placeholder `Pool` — zero reserves, filled in once a
+/// `refresh_pool`/`refresh_all` pass picks it up. `None` for a
+/// malformed/truncated log.
+fn decode_pool_created(log: &Log) -> Option<Pool> {
+    if log.topics.len() < 4 || log.data.len() < 64 {
+        return None;
+    }
+
+    let token0 = Address::from_slice(&log.topics[1].as_bytes()[12..32]);
+    let token1 = Address::from_slice(&log.topics[2].as_bytes()[12..32]);
+    let fee = U256::from_big_endian(log.topics[3].as_bytes()).as_u32();
+    let pool_addr = Address::from_slice(&log.data[32 + 12..64]);
+
+    Some(Pool {
+        address: pool_addr,
+        token0,
+        token1,
+        pool_type: PoolType::UniswapV3 { fee },
+        reserve0: U256::zero(),
+        reserve1: U256::zero(),
+        liquidity: U256::zero(),
+        fee_bps: fee / 100,
+        quote_mode: QuoteMode::for_pool_type(&PoolType::UniswapV3 { fee }),
+    })
+}
+
+/// Decodes a V2-style factory's `PairCreated(address indexed token0,
+/// address indexed token1, address pair, uint256)` log into a placeholder
+/// `Pool` for `pool_type` (zero reserves, as above). `None` for a
+/// malformed/truncated log.
+fn decode_pair_created(log: &Log, pool_type: PoolType) -> Option<Pool> {
+    if log.topics.len() < 3 || log.data.len() < 32 {
+        return None;
+    }
+
+    let token0 = Address::from_slice(&log.topics[1].as_bytes()[12..32]);
+    let token1 = Address::from_slice(&log.topics[2].as_bytes()[12..32]);
+    let pair_addr = Address::from_slice(&log.data[12..32]);
+
+    Some(Pool {
+        address: pair_addr,
+        token0,
+        token1,
+        quote_mode: QuoteMode::for_pool_type(&pool_type),
+        pool_type,
+        reserve0: U256::zero(),
+        reserve1: U256::zero(),
+        liquidity: U256::zero(),
+        fee_bps: 30,
+    })
 }
 
 /// Top tokens on Arbitrum for discovery - EXTENDED LIST
@@ -421,3 +1211,56 @@ pub fn get_top_arbitrum_tokens() -> Vec<(&'static str, Address)> {
         ("WINR", Address::from_str("0xD77B108d4f6cefaa0Cae9506A934e825BEccA46e").unwrap()),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wad_u256(n: u64) -> U256 {
+        U256::from(n) * wad()
+    }
+
+    /// For a perfectly balanced pool, the StableSwap invariant `D` is exactly
+    /// the sum of balances regardless of `amp` — a known, hand-checkable
+    /// fixed point of the Newton iteration.
+    #[test]
+    fn stable_get_d_balanced_pool_equals_sum_of_balances() {
+        let balances = [wad_u256(1_000_000), wad_u256(1_000_000)];
+        let d = stable_get_d(&balances, 100);
+        assert_eq!(d, balances[0] + balances[1]);
+    }
+
+    #[test]
+    fn stable_get_d_is_zero_for_empty_or_zero_balance() {
+        assert_eq!(stable_get_d(&[], 100), U256::zero());
+        assert_eq!(stable_get_d(&[U256::zero(), wad_u256(1)], 100), U256::zero());
+    }
+
+    /// Known-vector check for `stable_get_y`: starting from a balanced
+    /// 1,000,000 / 1,000,000 pool at amp=100, depositing 1,000 of token0 and
+    /// solving for the new token1 balance against the fixed `D` should come
+    /// back exactly equal to the value the same integer Newton iteration
+    /// gives when run independently in Python.
+    #[test]
+    fn stable_get_y_matches_known_vector() {
+        let balances = [wad_u256(1_000_000), wad_u256(1_000_000)];
+        let amp = 100;
+        let d = stable_get_d(&balances, amp);
+
+        let new_balance0 = balances[0] + wad_u256(1_000);
+        let new_balance1 = stable_get_y(d, amp, 2, &[new_balance0])
+            .expect("iteration should converge for an in-domain deposit");
+
+        let expected = U256::from(999_000_004_975_104_552_045_149u128);
+        assert_eq!(new_balance1, expected);
+
+        let amount_out = balances[1] - new_balance1;
+        assert_eq!(amount_out, U256::from(999_995_024_895_447_954_851u128));
+    }
+
+    #[test]
+    fn stable_get_y_rejects_zero_amp_or_zero_balance() {
+        assert!(stable_get_y(wad_u256(1), 0, 2, &[wad_u256(1)]).is_none());
+        assert!(stable_get_y(wad_u256(1), 100, 2, &[U256::zero()]).is_none());
+    }
+}
@@ -17,19 +17,146 @@ pub struct BenchResult {
     pub p50_ns: u64,
     pub p99_ns: u64,
     pub throughput_ops: f64,
+    /// Per-iteration cost from the `elapsed = slope * batch_size + intercept` fit.
+    /// Timer-read overhead is absorbed into `intercept_ns` instead of `slope_ns`.
+    pub slope_ns: f64,
+    pub intercept_ns: f64,
+    /// Bootstrap 95% confidence interval for `slope_ns` (2.5th/97.5th percentile).
+    pub slope_ci95: (f64, f64),
+    pub mild_outliers: usize,
+    pub severe_outliers: usize,
 }
 
 impl std::fmt::Display for BenchResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:<30} | avg: {:>8.2}ns | min: {:>6}ns | p50: {:>6}ns | p99: {:>6}ns | throughput: {:>12.0} ops/s",
-            self.name, self.avg_ns, self.min_ns, self.p50_ns, self.p99_ns, self.throughput_ops
+            "{:<30} | avg: {:>8.2}ns | slope: {:>7.2}ns [{:.2}, {:.2}] | min: {:>6}ns | p50: {:>6}ns | p99: {:>6}ns | outliers: {}/{} | throughput: {:>12.0} ops/s",
+            self.name, self.avg_ns, self.slope_ns, self.slope_ci95.0, self.slope_ci95.1,
+            self.min_ns, self.p50_ns, self.p99_ns,
+            self.mild_outliers, self.severe_outliers, self.throughput_ops
         )
     }
 }
 
-/// Run a benchmark with high precision
+/// Simple xorshift64* PRNG so bootstrap resampling doesn't need an external `rand` dependency.
+struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed | 1 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    /// Uniform index in `0..len`
+    fn next_index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+/// Fit `elapsed = slope * batch_size + intercept` via ordinary least squares.
+fn fit_line(pairs: &[(u64, u64)]) -> (f64, f64) {
+    let n = pairs.len() as f64;
+    let mean_x: f64 = pairs.iter().map(|(x, _)| *x as f64).sum::<f64>() / n;
+    let mean_y: f64 = pairs.iter().map(|(_, y)| *y as f64).sum::<f64>() / n;
+
+    let mut cov = 0.0;
+    let mut var = 0.0;
+    for (x, y) in pairs {
+        let dx = *x as f64 - mean_x;
+        cov += dx * (*y as f64 - mean_y);
+        var += dx * dx;
+    }
+
+    if var == 0.0 {
+        return (0.0, mean_y);
+    }
+
+    let slope = cov / var;
+    let intercept = mean_y - slope * mean_x;
+    (slope, intercept)
+}
+
+/// Bootstrap the slope's 95% confidence interval by resampling `(batch_size, elapsed_ns)`
+/// pairs with replacement and refitting.
+fn bootstrap_slope_ci(pairs: &[(u64, u64)], resamples: usize) -> (f64, f64) {
+    let mut rng = Xorshift64::new(pairs.iter().map(|(x, y)| x ^ y).fold(0x9E3779B97F4A7C15, |a, b| a ^ b));
+    let mut slopes = Vec::with_capacity(resamples);
+
+    for _ in 0..resamples {
+        let sample: Vec<(u64, u64)> = (0..pairs.len())
+            .map(|_| pairs[rng.next_index(pairs.len())])
+            .collect();
+        let (slope, _) = fit_line(&sample);
+        slopes.push(slope);
+    }
+
+    slopes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let lo = slopes[((slopes.len() as f64) * 0.025) as usize];
+    let hi = slopes[(((slopes.len() as f64) * 0.975) as usize).min(slopes.len() - 1)];
+    (lo, hi)
+}
+
+/// Tukey-fence outlier classification over per-iteration cost samples.
+/// Mild outliers lie outside `[Q1 - 1.5*IQR, Q3 + 1.5*IQR]`; severe outliers
+/// outside `[Q1 - 3*IQR, Q3 + 3*IQR]`.
+fn tukey_outliers(sorted_samples: &[f64]) -> (usize, usize) {
+    if sorted_samples.len() < 4 {
+        return (0, 0);
+    }
+
+    let q1 = percentile(sorted_samples, 0.25);
+    let q3 = percentile(sorted_samples, 0.75);
+    let iqr = q3 - q1;
+    let mild_lo = q1 - 1.5 * iqr;
+    let mild_hi = q3 + 1.5 * iqr;
+    let severe_lo = q1 - 3.0 * iqr;
+    let severe_hi = q3 + 3.0 * iqr;
+
+    let mut mild = 0;
+    let mut severe = 0;
+    for &x in sorted_samples {
+        if x < severe_lo || x > severe_hi {
+            severe += 1;
+        } else if x < mild_lo || x > mild_hi {
+            mild += 1;
+        }
+    }
+    (mild, severe)
+}
+
+/// Linear-interpolated percentile over an already-sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+    let rank = p * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    if lo == hi {
+        sorted[lo]
+    } else {
+        let frac = rank - lo as f64;
+        sorted[lo] * (1.0 - frac) + sorted[hi] * frac
+    }
+}
+
+/// Run a benchmark with high precision.
+///
+/// Per-iteration `Instant::now()` timing is dominated by clock-read overhead for
+/// sub-microsecond closures, so instead we time geometrically increasing batch
+/// sizes (1, 2, 4, ...) and fit `elapsed = slope * batch_size + intercept`; the
+/// timer overhead collapses into `intercept` and `slope` is the true per-call cost.
 pub fn run_bench<F>(name: &str, iterations: u64, mut f: F) -> BenchResult
 where
     F: FnMut(),
@@ -38,31 +165,42 @@ where
     for _ in 0..1000 {
         black_box(f());
     }
-    
-    // Collect samples
-    let mut samples = Vec::with_capacity(iterations as usize);
-    
-    for _ in 0..iterations {
+
+    let target_total = iterations.max(1000);
+    let mut pairs: Vec<(u64, u64)> = Vec::new();
+    let mut batch_size: u64 = 1;
+    let mut total_run: u64 = 0;
+
+    while total_run < target_total {
         let start = Instant::now();
-        black_box(f());
+        for _ in 0..batch_size {
+            black_box(f());
+        }
         let elapsed = start.elapsed().as_nanos() as u64;
-        samples.push(elapsed);
+        pairs.push((batch_size, elapsed));
+        total_run += batch_size;
+        batch_size = (batch_size * 2).min(target_total.saturating_sub(total_run)).max(1);
     }
-    
-    // Calculate stats
-    samples.sort_unstable();
-    
-    let total: u64 = samples.iter().sum();
-    let avg = total as f64 / iterations as f64;
-    let min = *samples.first().unwrap_or(&0);
-    let max = *samples.last().unwrap_or(&0);
-    let p50 = samples.get(samples.len() / 2).copied().unwrap_or(0);
-    let p99 = samples.get(samples.len() * 99 / 100).copied().unwrap_or(0);
-    let throughput = 1_000_000_000.0 / avg;
-    
+
+    let (slope, intercept) = fit_line(&pairs);
+    let slope_ci95 = bootstrap_slope_ci(&pairs, 2000);
+
+    // Per-iteration cost samples (one per batch) for the existing min/p50/p99/outlier view.
+    let mut per_iter: Vec<f64> = pairs.iter().map(|(b, e)| *e as f64 / *b as f64).collect();
+    per_iter.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (mild_outliers, severe_outliers) = tukey_outliers(&per_iter);
+
+    let total: u64 = pairs.iter().map(|(_, e)| *e).sum();
+    let min = per_iter.first().copied().unwrap_or(0.0).round() as u64;
+    let max = per_iter.last().copied().unwrap_or(0.0).round() as u64;
+    let p50 = percentile(&per_iter, 0.50).round() as u64;
+    let p99 = percentile(&per_iter, 0.99).round() as u64;
+    let avg = slope.max(0.0);
+    let throughput = if avg > 0.0 { 1_000_000_000.0 / avg } else { 0.0 };
+
     BenchResult {
         name: name.to_string(),
-        iterations,
+        iterations: total_run,
         total_ns: total,
         avg_ns: avg,
         min_ns: min,
@@ -70,6 +208,11 @@ where
         p50_ns: p50,
         p99_ns: p99,
         throughput_ops: throughput,
+        slope_ns: slope,
+        intercept_ns: intercept,
+        slope_ci95,
+        mild_outliers,
+        severe_outliers,
     }
 }
 
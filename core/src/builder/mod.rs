@@ -1,5 +1,9 @@
 //! Bundle Builder module
 
+mod signer;
+
+pub use signer::TransactionSigner;
+
 use crate::config::Config;
 use crate::types::{Bundle, BundleTransaction, Opportunity, OpportunityType};
 use std::sync::Arc;
@@ -11,6 +15,7 @@ pub struct BundleBuilder {
     config: Arc<Config>,
     count: AtomicU64,
     contract_address: Option<String>,
+    signer: Option<TransactionSigner>,
 }
 
 impl BundleBuilder {
@@ -19,6 +24,7 @@ impl BundleBuilder {
             config,
             count: AtomicU64::new(0),
             contract_address: None,
+            signer: None,
         }
     }
 
@@ -37,18 +43,43 @@ impl BundleBuilder {
         self.contract_address = Some(address);
     }
 
-    /// Build a bundle from an opportunity
-    pub async fn build(&self, opportunity: &Opportunity) -> anyhow::Result<Bundle> {
+    /// Configure the signer used to turn unsigned bundle transactions into
+    /// ready-to-relay signed bytes. Without a signer, `build` still returns
+    /// unsigned stubs (useful for dry-run / simulation-only setups).
+    pub fn set_signer(&mut self, private_key: &[u8; 32], chain_id: u64) -> anyhow::Result<()> {
+        self.signer = Some(TransactionSigner::new(private_key, chain_id)?);
+        Ok(())
+    }
+
+    /// Build a bundle from an opportunity.
+    ///
+    /// `starting_nonce` is the sender's next resolved nonce; transactions
+    /// within the bundle (e.g. a sandwich's frontrun/backrun pair) consume
+    /// consecutive nonces in order.
+    pub async fn build(&self, opportunity: &Opportunity, starting_nonce: u64) -> anyhow::Result<Bundle> {
         let contract = self.contract_address.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Contract address not set"))?;
 
-        let transactions = match opportunity.opportunity_type {
+        let mut transactions = match opportunity.opportunity_type {
             OpportunityType::Arbitrage => self.build_arbitrage_bundle(opportunity, contract)?,
             OpportunityType::Backrun => self.build_backrun_bundle(opportunity, contract)?,
             OpportunityType::Liquidation => self.build_liquidation_bundle(opportunity, contract)?,
             OpportunityType::Sandwich => self.build_sandwich_bundle(opportunity, contract)?,
         };
 
+        let default_max_fee = (self.config.strategy.default_max_fee_per_gas_gwei as u128) * 1_000_000_000;
+        for (i, tx) in transactions.iter_mut().enumerate() {
+            if tx.max_fee_per_gas.is_none() {
+                tx.max_fee_per_gas = Some(default_max_fee);
+            }
+            let nonce = starting_nonce + i as u64;
+            tx.nonce = Some(nonce);
+
+            if let Some(signer) = &self.signer {
+                tx.signed_raw = Some(signer.sign(tx, nonce)?);
+            }
+        }
+
         self.count.fetch_add(1, Ordering::Relaxed);
 
         Ok(Bundle {
@@ -84,6 +115,7 @@ impl BundleBuilder {
             max_priority_fee_per_gas: Some(1_000_000_000), // 1 gwei tip
             data: calldata,
             nonce: None,
+            signed_raw: None,
         }])
     }
 
@@ -112,6 +144,7 @@ impl BundleBuilder {
             max_priority_fee_per_gas: Some(2_000_000_000), // Higher tip for backrun
             data: calldata,
             nonce: None,
+            signed_raw: None,
         });
 
         Ok(txs)
@@ -139,6 +172,7 @@ impl BundleBuilder {
             max_priority_fee_per_gas: Some(1_000_000_000),
             data: calldata,
             nonce: None,
+            signed_raw: None,
         }])
     }
 
@@ -161,6 +195,7 @@ impl BundleBuilder {
             max_priority_fee_per_gas: Some(10_000_000_000), // High tip for frontrun
             data: frontrun_data.clone(),
             nonce: None,
+            signed_raw: None,
         });
 
         // Note: Target TX would be included by the bundle relay
@@ -175,6 +210,7 @@ impl BundleBuilder {
             max_priority_fee_per_gas: Some(1_000_000_000),
             data: frontrun_data, // Reverse swap
             nonce: None,
+            signed_raw: None,
         });
 
         Ok(txs)
@@ -0,0 +1,73 @@
+//! EIP-1559 transaction signing for bundle output
+
+use crate::ffi::hot_path::safe::{keccak256_fast, rlp_encode_address, rlp_encode_string, rlp_encode_u256, rlp_wrap_list_of_raw};
+use crate::types::BundleTransaction;
+use ethers::types::{Address, U256};
+use secp256k1::{Message, Secp256k1, SecretKey};
+
+/// Signs `BundleTransaction`s into raw EIP-1559 (`0x02`) transaction bytes.
+///
+/// Builds the unsigned payload `0x02 || rlp([chainId, nonce, maxPriorityFeePerGas,
+/// maxFeePerGas, gasLimit, to, value, data, accessList])`, signs its `keccak256`
+/// with `secp256k1` (RFC-6979 deterministic nonce), and appends `yParity`/`r`/`s`.
+pub struct TransactionSigner {
+    secret_key: SecretKey,
+    chain_id: u64,
+}
+
+impl TransactionSigner {
+    pub fn new(private_key: &[u8; 32], chain_id: u64) -> anyhow::Result<Self> {
+        let secret_key = SecretKey::from_slice(private_key)?;
+        Ok(Self { secret_key, chain_id })
+    }
+
+    /// Sign `tx` using `nonce`, returning the raw `0x02`-prefixed signed bytes.
+    pub fn sign(&self, tx: &BundleTransaction, nonce: u64) -> anyhow::Result<Vec<u8>> {
+        let to: Address = tx.to.parse()
+            .map_err(|_| anyhow::anyhow!("invalid `to` address: {}", tx.to))?;
+        let max_fee_per_gas = tx.max_fee_per_gas
+            .ok_or_else(|| anyhow::anyhow!("max_fee_per_gas not set"))?;
+        let max_priority_fee_per_gas = tx.max_priority_fee_per_gas.unwrap_or(0);
+
+        let unsigned_fields = vec![
+            rlp_encode_u256(U256::from(self.chain_id)),
+            rlp_encode_u256(U256::from(nonce)),
+            rlp_encode_u256(U256::from(max_priority_fee_per_gas)),
+            rlp_encode_u256(U256::from(max_fee_per_gas)),
+            rlp_encode_u256(U256::from(tx.gas_limit)),
+            rlp_encode_address(&to),
+            rlp_encode_u256(U256::from(tx.value)),
+            rlp_encode_string(&tx.data),
+            rlp_wrap_list_of_raw(&[]), // empty access list
+        ];
+
+        let mut unsigned = vec![0x02u8];
+        unsigned.extend_from_slice(&rlp_wrap_list_of_raw(&unsigned_fields));
+
+        let msg_hash = keccak256_fast(&unsigned);
+        let message = Message::from_digest_slice(msg_hash.as_bytes())?;
+        let secp = Secp256k1::signing_only();
+        let (recovery_id, sig) = secp
+            .sign_ecdsa_recoverable(&message, &self.secret_key)
+            .serialize_compact();
+
+        let signed_fields = vec![
+            rlp_encode_u256(U256::from(self.chain_id)),
+            rlp_encode_u256(U256::from(nonce)),
+            rlp_encode_u256(U256::from(max_priority_fee_per_gas)),
+            rlp_encode_u256(U256::from(max_fee_per_gas)),
+            rlp_encode_u256(U256::from(tx.gas_limit)),
+            rlp_encode_address(&to),
+            rlp_encode_u256(U256::from(tx.value)),
+            rlp_encode_string(&tx.data),
+            rlp_wrap_list_of_raw(&[]),
+            rlp_encode_u256(U256::from(recovery_id.to_i32() as u64)),
+            rlp_encode_u256(U256::from_big_endian(&sig[0..32])),
+            rlp_encode_u256(U256::from_big_endian(&sig[32..64])),
+        ];
+
+        let mut signed = vec![0x02u8];
+        signed.extend_from_slice(&rlp_wrap_list_of_raw(&signed_fields));
+        Ok(signed)
+    }
+}
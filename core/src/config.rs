@@ -20,6 +20,9 @@ pub struct Config {
     
     /// Logging settings
     pub logging: LoggingConfig,
+
+    /// Local control/telemetry endpoint settings
+    pub ipc: IpcConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +52,9 @@ pub struct StrategyConfig {
     pub max_gas_price_gwei: u64,
     pub slippage_tolerance_bps: u16,
     pub enabled_strategies: Vec<String>,
+    /// `max_fee_per_gas` to sign bundle transactions with when an opportunity
+    /// doesn't dictate a tighter bound of its own.
+    pub default_max_fee_per_gas_gwei: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +72,14 @@ pub struct LoggingConfig {
     pub file_path: Option<String>,
 }
 
+/// Where the local JSON-RPC control/telemetry server listens. Either field
+/// (or both) may be set; leaving both `None` disables the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcConfig {
+    pub socket_path: Option<String>,
+    pub tcp_addr: Option<String>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         let mut chains = HashMap::new();
@@ -108,6 +122,7 @@ impl Default for Config {
                     "arbitrage".to_string(),
                     "backrun".to_string(),
                 ],
+                default_max_fee_per_gas_gwei: 50,
             },
             performance: PerformanceConfig {
                 detector_threads: num_cpus::get(),
@@ -120,6 +135,10 @@ impl Default for Config {
                 json_output: true,
                 file_path: Some("logs/mev-engine.log".to_string()),
             },
+            ipc: IpcConfig {
+                socket_path: Some("/tmp/mev-engine.sock".to_string()),
+                tcp_addr: None,
+            },
         }
     }
 }
@@ -1,18 +1,64 @@
 //! Arbitrage detection
 
 use crate::config::Config;
+use crate::detector::state_reader::StateReader;
+use crate::detector::swap_parse;
 use crate::types::{Opportunity, OpportunityType, PendingTx, SwapInfo, DexType};
+use dashmap::DashMap;
+use ethers::types::{Address, U256};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::debug;
 
+/// How long a cached `(reserve_in, reserve_out)` read stays valid before
+/// `reserves_for` re-reads it on-chain. Reserves move every block a pool
+/// trades in, so a cache with no expiry would quote every "live" arbitrage
+/// decision after the first one against stale state.
+const RESERVE_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Resolves a `(dex, token_in, token_out)` trading pair to the on-chain pool
+/// address to read reserves/slot0 from. A decoded `SwapInfo` only carries
+/// the tokens involved — not which specific pool the swap routes through —
+/// so this is the missing link between detection and `StateReader`. Pool
+/// discovery (factory `getPair`/`getPool` lookups, event-log indexing) is
+/// somebody else's concern; this trait just exposes whatever index already
+/// has the answer.
+pub trait PoolResolver: Send + Sync {
+    fn resolve(&self, dex: DexType, token_in: Address, token_out: Address) -> Option<Address>;
+}
+
 /// Arbitrage detector for cross-DEX opportunities
 pub struct ArbitrageDetector {
     config: Arc<Config>,
+    state_reader: Option<Arc<dyn StateReader>>,
+    pool_resolver: Option<Arc<dyn PoolResolver>>,
+    /// `(reserve_in, reserve_out, read_at)` for a given venue/pair/direction,
+    /// so repeated quotes against the same pool within `RESERVE_CACHE_TTL`
+    /// don't re-issue the underlying `getReserves`/`slot0` call.
+    reserve_cache: DashMap<(DexType, Address, Address), (u128, u128, Instant)>,
 }
 
 impl ArbitrageDetector {
     pub fn new(config: Arc<Config>) -> Self {
-        Self { config }
+        Self {
+            config,
+            state_reader: None,
+            pool_resolver: None,
+            reserve_cache: DashMap::new(),
+        }
+    }
+
+    /// Read real pool reserves/slot0 instead of `get_cross_dex_prices`'s fixed
+    /// price table. Wired through for the cross-DEX quoting work to consume.
+    pub fn set_state_reader(&mut self, reader: Arc<dyn StateReader>) {
+        self.state_reader = Some(reader);
+    }
+
+    /// Wires in the pool index `get_cross_dex_prices` resolves trading pairs
+    /// against before it can call `StateReader`.
+    pub fn set_pool_resolver(&mut self, resolver: Arc<dyn PoolResolver>) {
+        self.pool_resolver = Some(resolver);
     }
 
     /// Detect arbitrage opportunity from pending transaction
@@ -46,82 +92,192 @@ impl ArbitrageDetector {
     }
 
     fn parse_swap(&self, tx: &PendingTx) -> Option<SwapInfo> {
-        // Parse calldata to extract swap info
-        let data = &tx.input;
-        
-        if data.len() < 4 {
-            return None;
-        }
+        swap_parse::parse_swap(&tx.input)
+    }
 
-        // Check function selector
-        let selector = &data[0..4];
-        
-        match selector {
-            // swapExactTokensForTokens (UniswapV2)
-            [0x38, 0xed, 0x17, 0x39] => self.parse_v2_swap(data),
-            // exactInputSingle (UniswapV3)
-            [0x41, 0x4b, 0xf3, 0x89] => self.parse_v3_swap(data),
-            _ => None,
+    /// Two-leg arbitrage sizing: simulate buying `swap.token_out` on
+    /// `swap.dex` with `swap.amount_in`, then selling it back for
+    /// `swap.token_in` on each candidate exit venue, accounting for price
+    /// impact on both legs via constant-product quoting. Returns
+    /// `(exit_dex, final_amount_of_token_in)` for every venue a real quote
+    /// could be computed for — `None` if the entry leg itself can't be
+    /// quoted (no resolver/reader wired up, or no reserves for this pair).
+    async fn get_cross_dex_prices(&self, swap: &SwapInfo) -> Option<Vec<(DexType, u128)>> {
+        let token_in = Address::from_str(&swap.token_in).ok()?;
+        let token_out = Address::from_str(&swap.token_out).ok()?;
+
+        let (entry_reserve_in, entry_reserve_out) =
+            self.reserves_for(swap.dex, token_in, token_out).await?;
+        let leg1_out = quote_constant_product(
+            swap.amount_in,
+            entry_reserve_in,
+            entry_reserve_out,
+            (swap.fee / 100) as u128,
+        )?;
+
+        const EXIT_CANDIDATES: [DexType; 3] =
+            [DexType::UniswapV2, DexType::SushiSwap, DexType::UniswapV3];
+
+        let mut results = Vec::new();
+        for exit_dex in EXIT_CANDIDATES {
+            if exit_dex == swap.dex {
+                continue;
+            }
+            let Some((exit_reserve_in, exit_reserve_out)) =
+                self.reserves_for(exit_dex, token_out, token_in).await
+            else {
+                continue;
+            };
+            let Some(final_out) = quote_constant_product(
+                leg1_out,
+                exit_reserve_in,
+                exit_reserve_out,
+                default_fee_bps(exit_dex),
+            ) else {
+                continue;
+            };
+
+            results.push((exit_dex, final_out));
         }
+
+        if results.is_empty() { None } else { Some(results) }
     }
 
-    fn parse_v2_swap(&self, data: &[u8]) -> Option<SwapInfo> {
-        if data.len() < 132 {
-            return None;
+    /// `(reserve_in, reserve_out)` for `token_in -> token_out` on `dex`,
+    /// cached for `RESERVE_CACHE_TTL` after each on-chain read. V3 pools
+    /// don't expose reserves directly, so their `slot0` is converted into
+    /// virtual reserves at the current tick.
+    async fn reserves_for(
+        &self,
+        dex: DexType,
+        token_in: Address,
+        token_out: Address,
+    ) -> Option<(u128, u128)> {
+        if let Some(cached) = self.reserve_cache.get(&(dex, token_in, token_out)) {
+            let (reserve_in, reserve_out, read_at) = *cached;
+            if read_at.elapsed() < RESERVE_CACHE_TTL {
+                return Some((reserve_in, reserve_out));
+            }
         }
 
-        // Decode: amountIn, amountOutMin, path[], to, deadline
-        let amount_in = u128::from_be_bytes(data[4..36].try_into().ok()?);
-        
-        // Path is dynamic, first address is token_in, last is token_out
-        // Simplified: assume 2-hop path at offset 128
-        let token_in = format!("0x{}", hex::encode(&data[100..120]));
-        let token_out = format!("0x{}", hex::encode(&data[132..152]));
-
-        Some(SwapInfo {
-            dex: DexType::UniswapV2,
-            token_in,
-            token_out,
-            amount_in,
-            amount_out_min: 0,
-            fee: 3000, // 0.3%
-        })
-    }
+        let resolver = self.pool_resolver.as_ref()?;
+        let state_reader = self.state_reader.as_ref()?;
+        let pool = resolver.resolve(dex, token_in, token_out)?;
 
-    fn parse_v3_swap(&self, _data: &[u8]) -> Option<SwapInfo> {
-        // TODO: Implement V3 parsing
-        None
-    }
+        let reserves = match dex {
+            DexType::UniswapV3 => {
+                let slot0 = state_reader.read_slot0(pool).await.ok()?;
+                let (reserve0, reserve1) =
+                    virtual_reserves(slot0.sqrt_price_x96, slot0.liquidity)?;
+                // `sqrtPriceX96` (and so `virtual_reserves`'s output) is
+                // always quoted token0-in-terms-of-token1; orient to
+                // (token_in, token_out) the same way the V2 branch below does.
+                if token_in < token_out {
+                    (reserve0, reserve1)
+                } else {
+                    (reserve1, reserve0)
+                }
+            }
+            _ => {
+                let reserves = state_reader.read_reserves(pool).await.ok()?;
+                // `getReserves` returns (reserve0, reserve1) in the pool's
+                // own token order; orient to (token_in, token_out) the same
+                // way the factory sorts the pair.
+                if token_in < token_out {
+                    (u256_to_u128(reserves.reserve0)?, u256_to_u128(reserves.reserve1)?)
+                } else {
+                    (u256_to_u128(reserves.reserve1)?, u256_to_u128(reserves.reserve0)?)
+                }
+            }
+        };
 
-    async fn get_cross_dex_prices(&self, swap: &SwapInfo) -> Option<Vec<(DexType, u128)>> {
-        // TODO: Query multiple DEXes for prices
-        // This would use on-chain calls or cached pool data
-        Some(vec![
-            (DexType::UniswapV2, 1_000_000),
-            (DexType::SushiSwap, 1_010_000),
-            (DexType::UniswapV3, 1_005_000),
-        ])
+        self.reserve_cache
+            .insert((dex, token_in, token_out), (reserves.0, reserves.1, Instant::now()));
+        Some(reserves)
     }
 
+    /// `expected_profit = finalOut - amount_in - gas_cost`, where `finalOut`
+    /// already accounts for price impact on both legs (replacing the old
+    /// `(exit - entry) * amount` linear approximation).
     fn calculate_profit(&self, swap: &SwapInfo, prices: &[(DexType, u128)]) -> Option<u128> {
-        // Find best exit price
-        let best_exit = prices.iter().max_by_key(|(_, p)| p)?;
-        let entry_price = 1_000_000u128; // Base price
-        
-        // Profit = (exit - entry) * amount - gas
-        let gross_profit = (best_exit.1.saturating_sub(entry_price)) * swap.amount_in / entry_price;
-        
+        let (_, final_out) = prices.iter().max_by_key(|(_, out)| *out)?;
+
         // Estimate gas cost (assume 50 gwei, 250k gas)
         let gas_cost = 250_000u128 * 50_000_000_000u128;
-        
-        gross_profit.checked_sub(gas_cost)
+
+        final_out.checked_sub(swap.amount_in)?.checked_sub(gas_cost)
     }
 
+    /// Picks the exit venue that maximizes the fully-simulated two-leg
+    /// output, not a raw mid price.
     fn find_best_exit_dex(&self, prices: &[(DexType, u128)]) -> DexType {
         prices
             .iter()
             .max_by_key(|(_, p)| p)
-            .map(|(d, _)| d.clone())
+            .map(|(d, _)| *d)
             .unwrap_or(DexType::UniswapV3)
     }
 }
+
+/// Standard constant-product quote with a fee taken out of the input leg:
+/// `amountOut = (amountIn * (10000 - feeBps) * reserveOut) / (reserveIn *
+/// 10000 + amountIn * (10000 - feeBps))`. Shared with `SandwichDetector`,
+/// which needs the exact same math to simulate a victim swap post-frontrun.
+pub(crate) fn quote_constant_product(
+    amount_in: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    fee_bps: u128,
+) -> Option<u128> {
+    if reserve_in == 0 || reserve_out == 0 {
+        return None;
+    }
+    let amount_in_after_fee = amount_in.checked_mul(10_000u128.checked_sub(fee_bps)?)?;
+    let numerator = amount_in_after_fee.checked_mul(reserve_out)?;
+    let denominator = reserve_in.checked_mul(10_000)?.checked_add(amount_in_after_fee)?;
+    if denominator == 0 {
+        return None;
+    }
+    Some(numerator / denominator)
+}
+
+/// Fee (out of 10,000) charged by a venue's default pool, used for the exit
+/// leg where — unlike the entry leg's decoded `swap.fee` — there's no tier
+/// already known from calldata.
+fn default_fee_bps(dex: DexType) -> u128 {
+    match dex {
+        DexType::UniswapV2 | DexType::SushiSwap => 30, // 0.3%
+        DexType::UniswapV3 => 30,                      // 0.3% tier, the common default
+        DexType::Curve | DexType::Balancer => 4,       // stable/weighted pools typically charge less
+    }
+}
+
+/// Approximates V3 concentrated liquidity as constant-product "virtual
+/// reserves" at the current tick: `reserve0 = L / sqrtP`, `reserve1 = L *
+/// sqrtP` where `sqrtP = sqrtPriceX96 / 2^96`. Exact only at the current
+/// tick — crossing ticks would change `L`, which this ignores — but it's
+/// the same order of approximation the rest of this detector's pricing
+/// already makes.
+fn virtual_reserves(sqrt_price_x96: U256, liquidity: u128) -> Option<(u128, u128)> {
+    if liquidity == 0 || sqrt_price_x96 > U256::from(u128::MAX) {
+        return None;
+    }
+    let sqrt_price = sqrt_price_x96.as_u128() as f64 / (1u128 << 96) as f64;
+    if sqrt_price <= 0.0 {
+        return None;
+    }
+    let reserve0 = (liquidity as f64 / sqrt_price) as u128;
+    let reserve1 = (liquidity as f64 * sqrt_price) as u128;
+    Some((reserve0, reserve1))
+}
+
+/// Safe `U256 -> u128` conversion — reserves come from untrusted/foreign
+/// calldata-adjacent RPC responses, so this must never panic the way
+/// `U256::as_u128()` would on an out-of-range value. Shared with
+/// `SandwichDetector` for the same reason.
+pub(crate) fn u256_to_u128(value: U256) -> Option<u128> {
+    if value > U256::from(u128::MAX) {
+        return None;
+    }
+    Some(value.as_u128())
+}
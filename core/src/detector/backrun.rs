@@ -1,35 +1,46 @@
 //! Backrun detection
 
 use crate::config::Config;
+use crate::detector::state_reader::StateReader;
 use crate::types::{Opportunity, OpportunityType, PendingTx, DexType};
+use ethers::types::{Address, U256};
 use std::sync::Arc;
 
 /// Backrun detector for large swaps
 pub struct BackrunDetector {
     config: Arc<Config>,
     min_swap_size_eth: u128,
+    state_reader: Option<Arc<dyn StateReader>>,
 }
 
 impl BackrunDetector {
     pub fn new(config: Arc<Config>) -> Self {
-        Self { 
+        Self {
             config,
             min_swap_size_eth: 10_000_000_000_000_000_000, // 10 ETH
+            state_reader: None,
         }
     }
 
+    /// Read real pool reserves for price-impact math instead of the fixed
+    /// "0.1% per 10 ETH" fallback.
+    pub fn set_state_reader(&mut self, reader: Arc<dyn StateReader>) {
+        self.state_reader = Some(reader);
+    }
+
     /// Detect backrun opportunity
     pub async fn detect(&self, tx: &PendingTx) -> Option<Opportunity> {
         // Check if transaction is a large swap
         let swap_size = self.estimate_swap_size(tx)?;
-        
+
         if swap_size < self.min_swap_size_eth {
             return None;
         }
 
-        // Estimate price impact
-        let price_impact = self.estimate_price_impact(swap_size)?;
-        
+        // Estimate price impact, using the target pool's live reserves when available
+        let pool = tx.to.map(Address::from);
+        let price_impact = self.estimate_price_impact(swap_size, pool).await?;
+
         // Calculate backrun profit
         let profit = self.calculate_backrun_profit(swap_size, price_impact)?;
 
@@ -68,11 +79,23 @@ impl BackrunDetector {
         None
     }
 
-    fn estimate_price_impact(&self, swap_size: u128) -> Option<u128> {
-        // Simplified price impact model
-        // Real implementation would query pool liquidity
-        
-        // Assume 0.1% impact per 10 ETH
+    async fn estimate_price_impact(&self, swap_size: u128, pool: Option<Address>) -> Option<u128> {
+        // Constant-product price impact: dx / (reserve0 + dx), in bps. The
+        // swap direction isn't decoded here, so `reserve0` is always used as
+        // the input-side reserve.
+        if let (Some(reader), Some(pool)) = (&self.state_reader, pool) {
+            if let Ok(reserves) = reader.read_reserves(pool).await {
+                let swap = U256::from(swap_size);
+                let denom = reserves.reserve0.saturating_add(swap);
+                if !denom.is_zero() {
+                    let impact_bps = swap.saturating_mul(U256::from(10_000)) / denom;
+                    return Some(impact_bps.min(U256::from(500)).as_u128()); // Cap at 5%
+                }
+            }
+        }
+
+        // No state reader configured (or the read failed) — fall back to the
+        // old fixed model: assume 0.1% impact per 10 ETH.
         let impact_bps = (swap_size / 10_000_000_000_000_000_000) * 10;
         Some(impact_bps.min(500)) // Cap at 5%
     }
@@ -1,10 +1,13 @@
 //! Liquidation detection
 
+use super::oracle::PriceOracle;
 use crate::config::Config;
 use crate::types::{Opportunity, OpportunityType, DexType};
 use std::sync::Arc;
 use std::collections::HashMap;
 
+const ONE_E18: u128 = 1_000_000_000_000_000_000;
+
 /// Position data from lending protocol
 #[derive(Debug, Clone)]
 pub struct Position {
@@ -14,6 +17,12 @@ pub struct Position {
     pub collateral_amount: u128,
     pub debt_token: String,
     pub debt_amount: u128,
+    /// Fraction of collateral value counted toward health, 18 decimals
+    /// (e.g. 0.85e18 for an 85% liquidation threshold).
+    pub liquidation_threshold: u128,
+    /// Last value reported by the lending protocol itself. Used only as a
+    /// fallback when no `PriceOracle` is wired up — once one is, health is
+    /// recomputed live in `find_liquidatable` instead of trusting this.
     pub health_factor: u128, // 18 decimals, <1e18 = liquidatable
 }
 
@@ -28,6 +37,7 @@ pub enum LendingProtocol {
 pub struct LiquidationDetector {
     config: Arc<Config>,
     positions: HashMap<String, Position>,
+    oracle: Option<Arc<PriceOracle>>,
 }
 
 impl LiquidationDetector {
@@ -35,9 +45,18 @@ impl LiquidationDetector {
         Self {
             config,
             positions: HashMap::new(),
+            oracle: None,
         }
     }
 
+    /// Point this detector at a live price oracle so `find_liquidatable`
+    /// recomputes health factors from collateral/debt prices on every call
+    /// instead of trusting whatever `health_factor` the position was last
+    /// updated with.
+    pub fn set_oracle(&mut self, oracle: Arc<PriceOracle>) {
+        self.oracle = Some(oracle);
+    }
+
     /// Update tracked positions
     pub fn update_positions(&mut self, positions: Vec<Position>) {
         for pos in positions {
@@ -45,13 +64,20 @@ impl LiquidationDetector {
         }
     }
 
-    /// Find liquidatable positions
-    pub fn find_liquidatable(&self) -> Vec<Opportunity> {
+    /// Find liquidatable positions, recomputing each position's health
+    /// factor from live oracle prices rather than trusting the stored value.
+    pub async fn find_liquidatable(&self) -> Vec<Opportunity> {
         let mut opportunities = Vec::new();
-        
-        for (_, position) in &self.positions {
+
+        for position in self.positions.values() {
+            let Some(health_factor) = self.compute_health_factor(position).await else {
+                // Collateral or debt oracle is invalid (stale/deviating) —
+                // skip the position rather than act on a bad price.
+                continue;
+            };
+
             // Health factor < 1e18 means liquidatable
-            if position.health_factor < 1_000_000_000_000_000_000 {
+            if health_factor < ONE_E18 {
                 if let Some(opp) = self.create_liquidation_opportunity(position) {
                     opportunities.push(opp);
                 }
@@ -61,6 +87,27 @@ impl LiquidationDetector {
         opportunities
     }
 
+    /// `health_factor = (collateral_value * liquidation_threshold) / debt_value`,
+    /// scaled to 1e18, using live oracle prices for both sides. Falls back to
+    /// the position's last reported `health_factor` if no oracle is wired up
+    /// yet. Returns `None` if either side's oracle reading is invalid.
+    async fn compute_health_factor(&self, position: &Position) -> Option<u128> {
+        let Some(oracle) = &self.oracle else {
+            return Some(position.health_factor);
+        };
+
+        let collateral_price = oracle.price(&position.collateral_token).await?;
+        let debt_price = oracle.price(&position.debt_token).await?;
+
+        let collateral_value = position.collateral_amount.saturating_mul(collateral_price) / ONE_E18;
+        let debt_value = position.debt_amount.saturating_mul(debt_price) / ONE_E18;
+        if debt_value == 0 {
+            return None;
+        }
+
+        Some(collateral_value.saturating_mul(position.liquidation_threshold) / debt_value)
+    }
+
     fn create_liquidation_opportunity(&self, position: &Position) -> Option<Opportunity> {
         // Calculate liquidation profit
         // Typically 5-10% bonus on liquidated collateral
@@ -104,8 +151,84 @@ impl LiquidationDetector {
 
     /// Monitor health factors via events
     pub async fn subscribe_health_updates(&self) -> anyhow::Result<()> {
-        // TODO: Subscribe to Borrow/Repay/Liquidation events
-        // Update internal position tracking
+        // TODO: Subscribe to Borrow/Repay/Liquidation events and update
+        // internal position tracking via `update_positions`. Health factors
+        // themselves no longer need to come through here — `find_liquidatable`
+        // recomputes them live from `oracle` on every call.
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::oracle::{CachedPriceSource, PriceOracle, PriceQuote};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    fn position(collateral_amount: u128, debt_amount: u128) -> Position {
+        Position {
+            user: "0xuser".into(),
+            protocol: LendingProtocol::AaveV3,
+            collateral_token: "WETH".into(),
+            collateral_amount,
+            debt_token: "USDC".into(),
+            debt_amount,
+            liquidation_threshold: 850_000_000_000_000_000, // 85%
+            health_factor: 0,
+        }
+    }
+
+    fn detector_with_oracle() -> (LiquidationDetector, Arc<CachedPriceSource>) {
+        let source = Arc::new(CachedPriceSource::new());
+        let oracle = Arc::new(PriceOracle::new(source.clone(), source.clone(), 60, 100));
+        let mut detector = LiquidationDetector::new(Arc::new(Config::default()));
+        detector.set_oracle(oracle);
+        (detector, source)
+    }
+
+    #[tokio::test]
+    async fn compute_health_factor_divides_by_debt_value() {
+        let (detector, source) = detector_with_oracle();
+        source.set_price("WETH", PriceQuote { price_1e18: ONE_E18, updated_at: now() });
+        source.set_price("USDC", PriceQuote { price_1e18: ONE_E18, updated_at: now() });
+
+        // 100 collateral @ 1.0 * 85% threshold / 50 debt @ 1.0 = 1.7e18.
+        let pos = position(100 * ONE_E18, 50 * ONE_E18);
+        let health_factor = detector.compute_health_factor(&pos).await;
+        assert_eq!(health_factor, Some(1_700_000_000_000_000_000));
+    }
+
+    #[tokio::test]
+    async fn compute_health_factor_returns_none_when_debt_value_is_zero() {
+        let (detector, source) = detector_with_oracle();
+        source.set_price("WETH", PriceQuote { price_1e18: ONE_E18, updated_at: now() });
+        source.set_price("USDC", PriceQuote { price_1e18: ONE_E18, updated_at: now() });
+
+        let pos = position(100 * ONE_E18, 0);
+        assert_eq!(detector.compute_health_factor(&pos).await, None);
+    }
+
+    #[tokio::test]
+    async fn compute_health_factor_falls_back_to_stored_value_without_an_oracle() {
+        let detector = LiquidationDetector::new(Arc::new(Config::default()));
+        let mut pos = position(100 * ONE_E18, 50 * ONE_E18);
+        pos.health_factor = 1_234;
+
+        assert_eq!(detector.compute_health_factor(&pos).await, Some(1_234));
+    }
+
+    #[tokio::test]
+    async fn compute_health_factor_returns_none_on_invalid_oracle_reading() {
+        let (detector, source) = detector_with_oracle();
+        // Only WETH has a price — USDC is missing, so the oracle reading for
+        // the debt side is invalid.
+        source.set_price("WETH", PriceQuote { price_1e18: ONE_E18, updated_at: now() });
+
+        let pos = position(100 * ONE_E18, 50 * ONE_E18);
+        assert_eq!(detector.compute_health_factor(&pos).await, None);
+    }
+}
@@ -4,10 +4,18 @@ mod arbitrage;
 mod backrun;
 mod liquidation;
 pub mod multi_threaded;
+pub mod oracle;
+pub mod pool_store;
+mod sandwich;
+pub mod state_reader;
+mod swap_parse;
 
 pub use arbitrage::ArbitrageDetector;
 pub use backrun::BackrunDetector;
-pub use liquidation::LiquidationDetector;
+pub use liquidation::{LiquidationDetector, Position, LendingProtocol};
+pub use sandwich::SandwichDetector;
+pub use oracle::{CachedPriceSource, PriceOracle, PriceQuote, PriceSource};
+pub use state_reader::{CachedStateReader, Reserves, RevmStateReader, RpcStateReader, Slot0, StateReader};
 pub use multi_threaded::{
     MultiThreadedDetector,
     DetectorConfig,
@@ -17,6 +25,7 @@ pub use multi_threaded::{
     DexType,
     PoolState,
 };
+pub use pool_store::{HotPoolCache, InMemoryPoolStore, MmapPoolStore, PoolStore};
 
 use crate::config::Config;
 use crate::types::{Opportunity, OpportunityType, PendingTx};
@@ -31,6 +40,7 @@ pub struct OpportunityDetector {
     arbitrage: ArbitrageDetector,
     backrun: BackrunDetector,
     liquidation: LiquidationDetector,
+    sandwich: SandwichDetector,
     count: AtomicU64,
     tx: Option<mpsc::Sender<Opportunity>>,
 }
@@ -42,11 +52,28 @@ impl OpportunityDetector {
             arbitrage: ArbitrageDetector::new(config.clone()),
             backrun: BackrunDetector::new(config.clone()),
             liquidation: LiquidationDetector::new(config.clone()),
+            sandwich: SandwichDetector::new(config.clone()),
             count: AtomicU64::new(0),
             tx: None,
         }
     }
 
+    /// Point the arbitrage, backrun and sandwich detectors at a shared
+    /// pool-state source so their price-impact math reads real reserves
+    /// instead of falling back on fixed constants.
+    pub fn set_state_reader(&mut self, reader: Arc<dyn StateReader>) {
+        self.arbitrage.set_state_reader(reader.clone());
+        self.backrun.set_state_reader(reader.clone());
+        self.sandwich.set_state_reader(reader);
+    }
+
+    /// Point the liquidation detector at a live price oracle so it recomputes
+    /// health factors from collateral/debt prices instead of trusting a
+    /// stored value.
+    pub fn set_oracle(&mut self, oracle: Arc<oracle::PriceOracle>) {
+        self.liquidation.set_oracle(oracle);
+    }
+
     pub async fn start(&self) -> anyhow::Result<()> {
         info!("Starting opportunity detector");
         Ok(())
@@ -73,6 +100,14 @@ impl OpportunityDetector {
             opportunities.push(opp);
         }
 
+        // Check for sandwich — this emits a linked frontrun/backrun pair
+        // sharing the victim's target_tx, not a single opportunity.
+        if let Some((front, back)) = self.sandwich.detect(&tx).await {
+            debug!("Sandwich opportunity found: {:?} / {:?}", front, back);
+            opportunities.push(front);
+            opportunities.push(back);
+        }
+
         // Update count
         self.count.fetch_add(opportunities.len() as u64, Ordering::Relaxed);
 
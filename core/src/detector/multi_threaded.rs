@@ -4,12 +4,13 @@
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
-use crossbeam_channel::{bounded, Sender, Receiver};
+use crossbeam_channel::{Sender, Receiver};
 use dashmap::DashMap;
 use ethers::types::{Address, U256, H256};
 use tracing::{info, debug, warn};
 
 use crate::mempool::ultra_ws::{MempoolTx, SwapInfo};
+use super::pool_store::{HotPoolCache, MmapPoolStore, PoolStore};
 
 /// Inline rdtsc for timing
 #[inline(always)]
@@ -105,6 +106,10 @@ pub struct MultiThreadedDetector {
     pools: Arc<DashMap<Address, PoolState>>,
     token_to_pools: Arc<DashMap<Address, Vec<Address>>>,
     stats: Arc<DetectorStats>,
+    /// Optional mmap-backed overflow store (behind a small in-RAM LRU) for chains
+    /// with more pools than fit in `pools`. When set, `load_pools`/`update_pool`
+    /// write through to it and misses on `pools` fall back to it.
+    disk_store: Option<Arc<HotPoolCache<MmapPoolStore>>>,
 }
 
 #[derive(Default)]
@@ -123,17 +128,26 @@ impl MultiThreadedDetector {
             pools: Arc::new(DashMap::new()),
             token_to_pools: Arc::new(DashMap::new()),
             stats: Arc::new(DetectorStats::default()),
+            disk_store: None,
         }
     }
-    
+
+    /// Enable the mmap-backed overflow store so pools beyond what fits in RAM can
+    /// still be indexed. `hot_capacity` bounds the in-RAM LRU sitting in front of it.
+    pub fn enable_disk_store(&mut self, path: impl AsRef<std::path::Path>, hot_capacity: usize) -> std::io::Result<()> {
+        let store = MmapPoolStore::open(path.as_ref())?;
+        self.disk_store = Some(Arc::new(HotPoolCache::new(Arc::new(store), hot_capacity)));
+        Ok(())
+    }
+
     /// Load pools into memory
     pub fn load_pools(&self, pools: Vec<PoolState>) {
         info!("Loading {} pools into detector", pools.len());
-        
+
         for pool in pools {
             // Index by pool address
             let pool_addr = pool.address;
-            
+
             // Index by token for fast lookup
             self.token_to_pools
                 .entry(pool.token0)
@@ -143,118 +157,126 @@ impl MultiThreadedDetector {
                 .entry(pool.token1)
                 .or_insert_with(Vec::new)
                 .push(pool_addr);
-            
-            self.pools.insert(pool_addr, pool);
+
+            if let Some(disk_store) = &self.disk_store {
+                disk_store.insert(pool);
+            } else {
+                self.pools.insert(pool_addr, pool);
+            }
         }
-        
+
         info!("Loaded pools. Token index size: {}", self.token_to_pools.len());
     }
-    
+
+    /// Look up a pool, falling through to the disk store (if enabled) on miss.
+    fn get_pool(&self, address: &Address) -> Option<PoolState> {
+        PoolLookup { pools: self.pools.clone(), disk_store: self.disk_store.clone() }.get(address)
+    }
+
     /// Start detector workers
+    ///
+    /// Batches are dispatched into a rayon thread pool and processed with
+    /// `par_iter`, so a transaction that fans out into a deep pool graph gets
+    /// work-stolen across cores instead of stalling whichever worker drew it.
+    /// This replaces the old fixed-thread-per-worker + `try_recv` busy-wait,
+    /// which idled on 50µs sleeps whenever the queue ran dry and couldn't
+    /// rebalance a heavy tx off of a stuck worker.
     pub fn start(
         &self,
         tx_receiver: Receiver<MempoolTx>,
         opp_sender: Sender<Opportunity>,
     ) {
         self.running.store(true, Ordering::SeqCst);
-        
+
         let num_workers = self.config.num_workers;
-        info!("Starting {} detector workers", num_workers);
-        
-        // Work distribution channel
-        let (work_tx, work_rx) = bounded::<MempoolTx>(10_000);
-        
-        // Spawn dispatcher thread
+        info!("Starting rayon detector pool with {} workers", num_workers);
+
+        let rayon_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_workers)
+            .thread_name(|i| format!("detector-{}", i))
+            .start_handler(|worker_id| {
+                #[cfg(target_os = "linux")]
+                {
+                    let core_ids = core_affinity::get_core_ids().unwrap_or_default();
+                    if let Some(core_id) = core_ids.get(worker_id + 2) {
+                        core_affinity::set_for_current(*core_id);
+                    }
+                }
+            })
+            .build()
+            .expect("Failed to build rayon detector pool");
+
+        let pool_lookup = PoolLookup { pools: self.pools.clone(), disk_store: self.disk_store.clone() };
+        let token_to_pools = self.token_to_pools.clone();
         let running = self.running.clone();
+        let config = self.config.clone();
         let stats = self.stats.clone();
+        let opp_id_seq = Arc::new(AtomicU64::new(0));
+
+        // Dispatcher: collect batches off the mempool channel and hand each
+        // batch to the rayon pool for work-stolen `par_iter` processing.
         thread::spawn(move || {
+            let mut batch: Vec<MempoolTx> = Vec::with_capacity(config.batch_size);
+
             while running.load(Ordering::SeqCst) {
-                match tx_receiver.recv_timeout(std::time::Duration::from_millis(10)) {
-                    Ok(tx) => {
-                        stats.txs_processed.fetch_add(1, Ordering::Relaxed);
-                        work_tx.send(tx).ok();
-                    }
-                    Err(_) => continue,
-                }
-            }
-        });
-        
-        // Spawn worker threads
-        for worker_id in 0..num_workers {
-            let work_rx = work_rx.clone();
-            let opp_sender = opp_sender.clone();
-            let pools = self.pools.clone();
-            let token_to_pools = self.token_to_pools.clone();
-            let running = self.running.clone();
-            let config = self.config.clone();
-            let stats = self.stats.clone();
-            
-            thread::Builder::new()
-                .name(format!("detector-{}", worker_id))
-                .spawn(move || {
-                    // Pin to CPU core
-                    #[cfg(target_os = "linux")]
-                    {
-                        use core_affinity::CoreId;
-                        let core_ids = core_affinity::get_core_ids().unwrap_or_default();
-                        if let Some(core_id) = core_ids.get(worker_id + 2) {
-                            core_affinity::set_for_current(*core_id);
+                batch.clear();
+                while batch.len() < config.batch_size {
+                    match tx_receiver.recv_timeout(std::time::Duration::from_millis(10)) {
+                        Ok(tx) => {
+                            stats.txs_processed.fetch_add(1, Ordering::Relaxed);
+                            batch.push(tx);
                         }
+                        Err(_) => break,
                     }
-                    
-                    let mut batch = Vec::with_capacity(config.batch_size);
-                    let mut opp_id = worker_id as u64 * 1_000_000;
-                    
-                    while running.load(Ordering::SeqCst) {
-                        // Collect batch
-                        batch.clear();
-                        while batch.len() < config.batch_size {
-                            match work_rx.try_recv() {
-                                Ok(tx) => batch.push(tx),
-                                Err(_) => break,
-                            }
-                        }
-                        
-                        if batch.is_empty() {
-                            thread::sleep(std::time::Duration::from_micros(50));
-                            continue;
+                }
+
+                if batch.is_empty() {
+                    continue;
+                }
+
+                let pools = pool_lookup.clone();
+                let token_to_pools = token_to_pools.clone();
+                let config = config.clone();
+                let stats = stats.clone();
+                let opp_sender = opp_sender.clone();
+                let opp_id_seq = opp_id_seq.clone();
+                let batch_to_process = std::mem::replace(&mut batch, Vec::with_capacity(config.batch_size));
+
+                rayon_pool.install(move || {
+                    use rayon::prelude::*;
+
+                    batch_to_process.par_iter().for_each(|tx| {
+                        if !tx.is_swap {
+                            return;
                         }
-                        
-                        // Process batch
-                        for tx in &batch {
-                            if !tx.is_swap {
-                                continue;
-                            }
-                            
-                            let detect_start = rdtsc();
-                            
-                            if let Some(swap_info) = &tx.swap_info {
-                                // Find arbitrage paths
-                                if let Some(opps) = find_arbitrage_paths(
-                                    swap_info,
-                                    &pools,
-                                    &token_to_pools,
-                                    &config,
-                                ) {
-                                    for mut opp in opps {
-                                        opp.id = opp_id;
-                                        opp.trigger_tx = tx.hash;
-                                        opp.detected_tsc = detect_start;
-                                        opp_id += 1;
-                                        
-                                        if opp.net_profit_wei >= config.min_profit_wei {
-                                            stats.profitable_count.fetch_add(1, Ordering::Relaxed);
-                                            opp_sender.send(opp).ok();
-                                        }
+
+                        let detect_start = rdtsc();
+
+                        if let Some(swap_info) = &tx.swap_info {
+                            if let Some(opps) = find_arbitrage_paths(
+                                swap_info,
+                                &pools,
+                                &token_to_pools,
+                                &config,
+                            ) {
+                                for mut opp in opps {
+                                    opp.id = opp_id_seq.fetch_add(1, Ordering::Relaxed);
+                                    opp.trigger_tx = tx.hash;
+                                    opp.detected_tsc = detect_start;
+
+                                    if opp.net_profit_wei >= config.min_profit_wei {
+                                        stats.profitable_count.fetch_add(1, Ordering::Relaxed);
+                                        opp_sender.send(opp).ok();
                                     }
                                 }
                             }
-                            
-                            stats.opportunities_found.fetch_add(1, Ordering::Relaxed);
                         }
-                    }
-                }).expect("Failed to spawn detector worker");
-        }
+
+                        stats.opportunities_found.fetch_add(1, Ordering::Relaxed);
+                    });
+                });
+            }
+        });
     }
     
     pub fn stop(&self) {
@@ -270,153 +292,338 @@ impl MultiThreadedDetector {
             pool.reserve0 = reserve0;
             pool.reserve1 = reserve1;
             pool.last_update = rdtsc();
+            return;
+        }
+
+        if let Some(disk_store) = &self.disk_store {
+            disk_store.update_reserves(address, reserve0, reserve1);
         }
     }
 }
 
-/// Find arbitrage paths using graph traversal
-fn find_arbitrage_paths(
-    swap: &SwapInfo,
-    pools: &DashMap<Address, PoolState>,
+/// Point-lookup view over pool state used by the rayon dispatch path —
+/// checks the in-RAM `pools` map first, then reads through `disk_store` (if
+/// enabled) on a miss, same as `MultiThreadedDetector::get_pool`. The
+/// dispatch path (`build_local_graph`/`simulate_cycle_exact`/
+/// `find_arbitrage_paths`) only ever does point lookups by address, so this
+/// is all it needs — it doesn't require enumerating every pool in `pools`.
+#[derive(Clone)]
+struct PoolLookup {
+    pools: Arc<DashMap<Address, PoolState>>,
+    disk_store: Option<Arc<HotPoolCache<MmapPoolStore>>>,
+}
+
+impl PoolLookup {
+    fn get(&self, address: &Address) -> Option<PoolState> {
+        if let Some(pool) = self.pools.get(address) {
+            return Some(pool.clone());
+        }
+        self.disk_store.as_ref()?.get(address)
+    }
+}
+
+/// A directed edge in the local swap graph: swapping through `pool` from
+/// `token_in` to `token_out` at the marginal (first-order) rate.
+#[derive(Clone, Copy)]
+struct GraphEdge {
+    pool: Address,
+    token_in: Address,
+    token_out: Address,
+    weight: f64, // -ln(effective_rate)
+}
+
+/// Marginal output per unit input for one pool direction, fee included.
+/// This is the first-order (infinitesimal-trade) rate; the log-linearized
+/// graph built from it is only a guide for *which* cycle to try, since real
+/// constant-product rates are amount-dependent.
+fn effective_rate(pool: &PoolState, token_in: Address) -> Option<f64> {
+    let (reserve_in, reserve_out) = if pool.token0 == token_in {
+        (pool.reserve0, pool.reserve1)
+    } else if pool.token1 == token_in {
+        (pool.reserve1, pool.reserve0)
+    } else {
+        return None;
+    };
+
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return None;
+    }
+
+    let fee_factor = (10000 - pool.fee) as f64 / 10000.0;
+    let r_in = reserve_in.as_u128() as f64;
+    let r_out = reserve_out.as_u128() as f64;
+    Some((r_out / r_in) * fee_factor)
+}
+
+/// Breadth-expand a local edge set reachable from `start` within `max_hops`,
+/// so Bellman-Ford only ever sees the pools that could plausibly close a
+/// cycle back to `start` in the hop budget, not the whole pool universe.
+fn build_local_graph(
+    start: Address,
+    pools: &PoolLookup,
     token_to_pools: &DashMap<Address, Vec<Address>>,
-    config: &DetectorConfig,
-) -> Option<Vec<Opportunity>> {
-    let mut opportunities = Vec::new();
-    
-    // Get pools containing output token
-    let output_pools = token_to_pools.get(&swap.token_out)?;
-    
-    // Prefetch pool data (noop for now)
-    let _ = output_pools.iter().take(8).count();
-    
-    // 2-hop paths: token_out -> intermediate -> token_in
-    for pool1_addr in output_pools.iter() {
-        if let Some(pool1) = pools.get(pool1_addr) {
-            let intermediate = if pool1.token0 == swap.token_out {
-                pool1.token1
-            } else if pool1.token1 == swap.token_out {
-                pool1.token0
-            } else {
+    max_hops: usize,
+) -> Vec<GraphEdge> {
+    use std::collections::{HashSet, VecDeque};
+
+    let mut edges = Vec::new();
+    let mut visited_tokens: HashSet<Address> = HashSet::new();
+    let mut visited_pools: HashSet<Address> = HashSet::new();
+    let mut frontier: VecDeque<(Address, usize)> = VecDeque::new();
+
+    visited_tokens.insert(start);
+    frontier.push_back((start, 0));
+
+    while let Some((token, depth)) = frontier.pop_front() {
+        if depth >= max_hops {
+            continue;
+        }
+
+        let Some(pool_addrs) = token_to_pools.get(&token) else { continue };
+
+        for pool_addr in pool_addrs.iter() {
+            if !visited_pools.insert(*pool_addr) {
                 continue;
-            };
-            
-            // Find pool back to token_in
-            if let Some(return_pools) = token_to_pools.get(&intermediate) {
-                for pool2_addr in return_pools.iter() {
-                    if let Some(pool2) = pools.get(pool2_addr) {
-                        // Check if pool2 contains token_in
-                        if pool2.token0 == swap.token_in || pool2.token1 == swap.token_in {
-                            // Calculate profitability
-                            if let Some(opp) = calculate_2hop_profit(
-                                swap,
-                                pool1.value(),
-                                pool2.value(),
-                                config,
-                            ) {
-                                opportunities.push(opp);
-                            }
-                        }
-                    }
+            }
+            let Some(pool) = pools.get(pool_addr) else { continue };
+
+            let (a, b) = (pool.token0, pool.token1);
+            if let Some(rate_ab) = effective_rate(&pool, a) {
+                edges.push(GraphEdge { pool: *pool_addr, token_in: a, token_out: b, weight: -rate_ab.ln() });
+            }
+            if let Some(rate_ba) = effective_rate(&pool, b) {
+                edges.push(GraphEdge { pool: *pool_addr, token_in: b, token_out: a, weight: -rate_ba.ln() });
+            }
+
+            for next in [a, b] {
+                if visited_tokens.insert(next) {
+                    frontier.push_back((next, depth + 1));
                 }
             }
         }
     }
-    
-    // Triangular: Same as 2-hop but with explicit WETH path
-    // (Simplified - real implementation has more paths)
-    
-    if opportunities.is_empty() {
-        None
-    } else {
-        Some(opportunities)
+
+    edges
+}
+
+/// Bellman-Ford relaxation bounded to `max_hops` edges, looking for a
+/// negative-weight cycle back to `start` (i.e. `product(effective_rate) > 1`).
+/// Returns the recovered cycle as a sequence of `(pool, token_in, token_out)`
+/// steps, or `None` if no profitable cycle was found within the hop budget.
+fn find_negative_cycle(
+    start: Address,
+    edges: &[GraphEdge],
+    max_hops: usize,
+) -> Option<Vec<GraphEdge>> {
+    use std::collections::HashMap;
+
+    let mut dist: HashMap<Address, f64> = HashMap::new();
+    let mut pred: HashMap<Address, GraphEdge> = HashMap::new();
+    dist.insert(start, 0.0);
+
+    for _ in 0..max_hops {
+        let mut updated = false;
+        for edge in edges {
+            let Some(&d_u) = dist.get(&edge.token_in) else { continue };
+            let d_v = d_u + edge.weight;
+            if d_v < *dist.get(&edge.token_out).unwrap_or(&f64::INFINITY) - 1e-12 {
+                dist.insert(edge.token_out, d_v);
+                pred.insert(edge.token_out, *edge);
+                updated = true;
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+
+    // One extra relaxation pass: any edge that still improves a node reachable
+    // from `start` sits on (or downstream of) a negative cycle.
+    let mut cycle_node = None;
+    for edge in edges {
+        let Some(&d_u) = dist.get(&edge.token_in) else { continue };
+        let d_v = d_u + edge.weight;
+        if d_v < *dist.get(&edge.token_out).unwrap_or(&f64::INFINITY) - 1e-12 {
+            cycle_node = Some(edge.token_out);
+            break;
+        }
+    }
+
+    let mut node = cycle_node?;
+    // Walk predecessors max_hops times to land inside the cycle itself.
+    for _ in 0..max_hops {
+        node = pred.get(&node)?.token_in;
+    }
+
+    // Walk the cycle out from `node` back to `node`.
+    let cycle_start = node;
+    let mut steps = Vec::new();
+    loop {
+        let edge = *pred.get(&node)?;
+        steps.push(edge);
+        node = edge.token_in;
+        if node == cycle_start || steps.len() > max_hops {
+            break;
+        }
+    }
+    steps.reverse();
+
+    if steps.is_empty() || steps.first()?.token_in != steps.last()?.token_out {
+        return None;
     }
+
+    // Only a cycle through `start` counts; rotate it so it begins there.
+    let start_pos = steps.iter().position(|e| e.token_in == start)?;
+    steps.rotate_left(start_pos);
+
+    Some(steps)
 }
 
-/// Calculate 2-hop arbitrage profit
-#[inline]
-fn calculate_2hop_profit(
-    swap: &SwapInfo,
-    pool1: &PoolState,
-    pool2: &PoolState,
+/// Re-simulate a recovered cycle forward with concrete `amount_in` using exact
+/// `U256` constant-product math (the log-linear graph above is only a
+/// first-order guide; real rates are amount-dependent). Sweeps a handful of
+/// input sizes to approximate the profit-maximizing amount and returns the
+/// best one, re-validated for strict profitability in integer arithmetic.
+fn simulate_cycle_exact(
+    cycle: &[GraphEdge],
+    pools: &PoolLookup,
     config: &DetectorConfig,
-) -> Option<Opportunity> {
-    // Get amount out from victim swap (simplified simulation)
-    let victim_out = simulate_swap_out(
-        swap.amount_in,
-        swap.token_in,
-        swap.token_out,
-        pool1,
-    )?;
-    
-    // Our backrun: swap token_out -> intermediate
-    let intermediate = if pool1.token0 == swap.token_out {
-        pool1.token1
+) -> Option<(U256, Vec<PathStep>, U256)> {
+    let sweep_fractions: &[u64] = &[1, 5, 10, 25, 50]; // basis points of the first pool's reserve
+
+    let first_pool = pools.get(&cycle[0].pool)?;
+    let base_reserve = if first_pool.token0 == cycle[0].token_in {
+        first_pool.reserve0
     } else {
-        pool1.token0
+        first_pool.reserve1
     };
-    
-    let step1_out = simulate_swap_out(
-        victim_out / 10, // Use 10% of liquidity
-        swap.token_out,
-        intermediate,
-        pool1,
-    )?;
-    
-    // intermediate -> token_in
-    let step2_out = simulate_swap_out(
-        step1_out,
-        intermediate,
-        swap.token_in,
-        pool2,
-    )?;
-    
-    // Calculate profit
-    let input_amount = victim_out / 10;
-    if step2_out <= input_amount {
+
+    if base_reserve.is_zero() {
         return None;
     }
-    
-    let gross_profit = step2_out - input_amount;
-    let gas_cost = config.gas_price * U256::from(200_000); // Estimate
-    
+
+    let mut best: Option<(U256, Vec<PathStep>, U256)> = None;
+
+    for bps in sweep_fractions {
+        let amount_in = base_reserve * U256::from(*bps) / U256::from(10_000u32);
+        if amount_in.is_zero() {
+            continue;
+        }
+
+        let mut current_amount = amount_in;
+        let mut steps = Vec::with_capacity(cycle.len());
+        let mut valid = true;
+
+        for edge in cycle {
+            let Some(pool) = pools.get(&edge.pool) else { valid = false; break };
+            let Some(out) = simulate_swap_out(current_amount, edge.token_in, edge.token_out, &pool) else {
+                valid = false;
+                break;
+            };
+            if out.is_zero() {
+                valid = false;
+                break;
+            }
+
+            steps.push(PathStep {
+                pool: edge.pool,
+                token_in: edge.token_in,
+                token_out: edge.token_out,
+                amount_in: current_amount,
+                expected_out: out,
+                dex_type: pool.dex_type,
+            });
+            current_amount = out;
+        }
+
+        if !valid || current_amount <= amount_in {
+            continue;
+        }
+
+        let profit = current_amount - amount_in;
+        let is_better = best.as_ref().map(|(_, _, p)| profit > *p).unwrap_or(true);
+        if is_better {
+            best = Some((amount_in, steps, profit));
+        }
+    }
+
+    let (amount_in, steps, gross_profit) = best?;
+    let gas_estimate = U256::from(120_000u64 + 80_000u64 * cycle.len() as u64);
+    let gas_cost = config.gas_price * gas_estimate;
+
     if gross_profit <= gas_cost {
         return None;
     }
-    
-    let net_profit = gross_profit - gas_cost;
-    
-    Some(Opportunity {
-        id: 0,
-        detected_tsc: 0,
-        detected_ns: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64,
-        trigger_tx: H256::zero(),
-        path: vec![
-            PathStep {
-                pool: pool1.address,
-                token_in: swap.token_out,
-                token_out: intermediate,
-                amount_in: input_amount,
-                expected_out: step1_out,
-                dex_type: pool1.dex_type,
-            },
-            PathStep {
-                pool: pool2.address,
-                token_in: intermediate,
-                token_out: swap.token_in,
-                amount_in: step1_out,
-                expected_out: step2_out,
-                dex_type: pool2.dex_type,
-            },
-        ],
-        profit_wei: gross_profit,
-        gas_estimate: U256::from(200_000),
-        net_profit_wei: net_profit,
-        confidence: 0.8,
-        expires_block: 0,
-    })
+
+    Some((amount_in, steps, gross_profit - gas_cost))
+}
+
+/// Find arbitrage paths using a graph-based negative-cycle search.
+///
+/// Builds a directed graph from the local pool neighborhood around the
+/// victim's `token_out` (two edges per pool, weighted `-ln(effective_rate)`),
+/// runs Bellman-Ford bounded to `config.max_hops`, and recovers a cycle through
+/// `token_out` with negative total weight (i.e. `product(rate) > 1`) as a
+/// candidate arbitrage.
+/// Candidates are re-simulated with concrete amounts in exact `U256` math
+/// before being emitted, since the log-linearized graph is only a first-order
+/// guide and constant-product rates are amount-dependent.
+fn find_arbitrage_paths(
+    swap: &SwapInfo,
+    pools: &PoolLookup,
+    token_to_pools: &DashMap<Address, Vec<Address>>,
+    config: &DetectorConfig,
+) -> Option<Vec<Opportunity>> {
+    const MAX_CANDIDATES_PER_TX: usize = 4;
+
+    let edges = build_local_graph(swap.token_out, pools, token_to_pools, config.max_hops);
+    if edges.is_empty() {
+        return None;
+    }
+
+    let mut opportunities = Vec::new();
+    let mut remaining_edges = edges;
+
+    // Cap enumeration per trigger tx: each recovered cycle removes its pools
+    // from the candidate set so a repeated search surfaces a different cycle.
+    for _ in 0..MAX_CANDIDATES_PER_TX {
+        let Some(cycle) = find_negative_cycle(swap.token_out, &remaining_edges, config.max_hops) else {
+            break;
+        };
+
+        if let Some((amount_in, steps, net_profit_wei)) = simulate_cycle_exact(&cycle, pools, config) {
+            let profit_wei = steps.last().map(|s| s.expected_out).unwrap_or_default().saturating_sub(amount_in);
+            let gas_estimate = U256::from(120_000u64 + 80_000u64 * cycle.len() as u64);
+
+            opportunities.push(Opportunity {
+                id: 0,
+                detected_tsc: 0,
+                detected_ns: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_nanos() as u64,
+                trigger_tx: H256::zero(),
+                path: steps,
+                profit_wei,
+                gas_estimate,
+                net_profit_wei,
+                confidence: 0.6,
+                expires_block: 0,
+            });
+        }
+
+        let used_pools: std::collections::HashSet<Address> = cycle.iter().map(|e| e.pool).collect();
+        remaining_edges.retain(|e| !used_pools.contains(&e.pool));
+        if remaining_edges.is_empty() {
+            break;
+        }
+    }
+
+    if opportunities.is_empty() {
+        None
+    } else {
+        Some(opportunities)
+    }
 }
 
 /// Simulate swap output using constant product formula
@@ -0,0 +1,183 @@
+//! Price oracle subsystem for liquidation health-factor accounting
+//!
+//! `LiquidationDetector` used to trust a pre-computed `health_factor` on
+//! every `Position`, so it could never actually tell a position had moved
+//! since the last external update. This gives it its own price feed: a
+//! primary source (e.g. a Chainlink aggregator) plus a DEX-pool-derived
+//! fallback, mirroring Mango v4's pattern of adding a CLMM pool as an oracle
+//! fallback. An oracle reading is marked invalid — and the position it backs
+//! is skipped rather than mispriced — if it's stale or the two sources
+//! disagree beyond a configured band.
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single price reading, 1e18-scaled, in a common quote unit (e.g. USD).
+#[derive(Debug, Clone, Copy)]
+pub struct PriceQuote {
+    pub price_1e18: u128,
+    pub updated_at: u64, // unix seconds
+}
+
+/// A source of token prices, keyed by token symbol/address string (matching
+/// `Position::collateral_token`/`debt_token`).
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    async fn price(&self, token: &str) -> Option<PriceQuote>;
+}
+
+/// Feed-map-backed price source. Used both as the primary (fed from a
+/// Chainlink-style push/poll job) and, with a second instance, as the
+/// DEX-pool fallback (kept warm from `PoolManager` spot prices) — this crate
+/// doesn't yet have a direct Chainlink RPC client or a generic token-string
+/// keyed pool index, so both sides are populated externally via `set_price`.
+#[derive(Default)]
+pub struct CachedPriceSource {
+    prices: DashMap<String, PriceQuote>,
+}
+
+impl CachedPriceSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_price(&self, token: &str, quote: PriceQuote) {
+        self.prices.insert(token.to_string(), quote);
+    }
+}
+
+#[async_trait]
+impl PriceSource for CachedPriceSource {
+    async fn price(&self, token: &str) -> Option<PriceQuote> {
+        self.prices.get(token).map(|q| *q)
+    }
+}
+
+/// Resolves a token's price from a primary source with a DEX-pool fallback,
+/// rejecting (rather than guessing through) stale or disagreeing reads.
+pub struct PriceOracle {
+    primary: Arc<dyn PriceSource>,
+    fallback: Arc<dyn PriceSource>,
+    max_staleness_secs: u64,
+    max_deviation_bps: u64,
+}
+
+impl PriceOracle {
+    pub fn new(
+        primary: Arc<dyn PriceSource>,
+        fallback: Arc<dyn PriceSource>,
+        max_staleness_secs: u64,
+        max_deviation_bps: u64,
+    ) -> Self {
+        Self { primary, fallback, max_staleness_secs, max_deviation_bps }
+    }
+
+    /// Resolve `token`'s price, preferring a fresh primary reading but
+    /// falling back to the pool-derived source when the primary is stale or
+    /// missing. Returns `None` — "invalid oracle" — if neither source has a
+    /// usable reading, or if both do but disagree beyond `max_deviation_bps`.
+    pub async fn price(&self, token: &str) -> Option<u128> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        let primary = self.primary.price(token).await
+            .filter(|q| now.saturating_sub(q.updated_at) <= self.max_staleness_secs);
+        let fallback = self.fallback.price(token).await
+            .filter(|q| now.saturating_sub(q.updated_at) <= self.max_staleness_secs);
+
+        match (primary, fallback) {
+            (Some(p), Some(f)) => {
+                if deviates_beyond(p.price_1e18, f.price_1e18, self.max_deviation_bps) {
+                    None
+                } else {
+                    Some(p.price_1e18)
+                }
+            }
+            (Some(p), None) => Some(p.price_1e18),
+            (None, Some(f)) => Some(f.price_1e18),
+            (None, None) => None,
+        }
+    }
+}
+
+/// `true` if `a` and `b` differ by more than `max_bps` of the smaller value.
+fn deviates_beyond(a: u128, b: u128, max_bps: u64) -> bool {
+    let (hi, lo) = if a > b { (a, b) } else { (b, a) };
+    if lo == 0 {
+        return hi != 0;
+    }
+    let diff_bps = (hi - lo) * 10_000 / lo;
+    diff_bps > max_bps as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_STALENESS_SECS: u64 = 60;
+    const MAX_DEVIATION_BPS: u64 = 100; // 1%
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    fn oracle() -> (Arc<CachedPriceSource>, Arc<CachedPriceSource>, PriceOracle) {
+        let primary = Arc::new(CachedPriceSource::new());
+        let fallback = Arc::new(CachedPriceSource::new());
+        let price_oracle = PriceOracle::new(
+            primary.clone(),
+            fallback.clone(),
+            MAX_STALENESS_SECS,
+            MAX_DEVIATION_BPS,
+        );
+        (primary, fallback, price_oracle)
+    }
+
+    #[tokio::test]
+    async fn both_sources_fresh_and_within_band_returns_a_price() {
+        let (primary, fallback, oracle) = oracle();
+        primary.set_price("WETH", PriceQuote { price_1e18: 2_000 * ONE, updated_at: now() });
+        fallback.set_price("WETH", PriceQuote { price_1e18: 2_001 * ONE, updated_at: now() });
+
+        assert_eq!(oracle.price("WETH").await, Some(2_000 * ONE));
+    }
+
+    #[tokio::test]
+    async fn both_sources_fresh_but_beyond_band_returns_none() {
+        let (primary, fallback, oracle) = oracle();
+        primary.set_price("WETH", PriceQuote { price_1e18: 2_000 * ONE, updated_at: now() });
+        fallback.set_price("WETH", PriceQuote { price_1e18: 2_200 * ONE, updated_at: now() }); // 10% off
+
+        assert_eq!(oracle.price("WETH").await, None);
+    }
+
+    #[tokio::test]
+    async fn one_source_stale_falls_back_to_the_other() {
+        let (primary, fallback, oracle) = oracle();
+        primary.set_price("WETH", PriceQuote { price_1e18: 2_000 * ONE, updated_at: now() - MAX_STALENESS_SECS - 1 });
+        fallback.set_price("WETH", PriceQuote { price_1e18: 2_001 * ONE, updated_at: now() });
+
+        assert_eq!(oracle.price("WETH").await, Some(2_001 * ONE));
+    }
+
+    #[tokio::test]
+    async fn both_sources_stale_returns_none() {
+        let (primary, fallback, oracle) = oracle();
+        primary.set_price("WETH", PriceQuote { price_1e18: 2_000 * ONE, updated_at: now() - MAX_STALENESS_SECS - 1 });
+        fallback.set_price("WETH", PriceQuote { price_1e18: 2_001 * ONE, updated_at: now() - MAX_STALENESS_SECS - 1 });
+
+        assert_eq!(oracle.price("WETH").await, None);
+    }
+
+    const ONE: u128 = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn deviates_beyond_is_symmetric_and_respects_the_band() {
+        assert!(!deviates_beyond(100, 101, 100)); // 1% diff, 1% band -> within
+        assert!(deviates_beyond(100, 102, 100)); // ~2% diff, 1% band -> beyond
+        assert!(deviates_beyond(102, 100, 100)); // order shouldn't matter
+        assert!(!deviates_beyond(0, 0, 0));
+        assert!(deviates_beyond(0, 1, 0));
+    }
+}
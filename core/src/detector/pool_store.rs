@@ -0,0 +1,379 @@
+//! Disk-backed pool storage so the detector isn't bounded by RAM
+//!
+//! `MultiThreadedDetector` historically kept every `PoolState` in an in-memory
+//! `DashMap`, which caps the number of trackable pools at available RAM. The
+//! `PoolStore` trait abstracts over where pool state actually lives; `MmapPoolStore`
+//! is a bucketed hash table persisted in an mmap'd file so the full pool universe
+//! on an L2/L3 chain can be indexed without OOM, while `HotPoolCache` keeps the
+//! most-traded pools in RAM in front of it for the detection hot path.
+
+use std::fs::OpenOptions;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+use dashmap::DashMap;
+use ethers::types::{Address, U256};
+use memmap2::{MmapMut, MmapOptions};
+use tiny_keccak::{Hasher, Keccak};
+use tracing::{debug, info};
+
+use super::multi_threaded::{DexType, PoolState};
+
+/// Backend-agnostic pool storage used by the detector's hot path.
+pub trait PoolStore: Send + Sync {
+    fn get(&self, address: &Address) -> Option<PoolState>;
+    fn update_reserves(&self, address: Address, reserve0: U256, reserve1: U256) -> bool;
+    fn insert(&self, pool: PoolState);
+    fn len(&self) -> usize;
+}
+
+/// Plain in-memory backend, equivalent to the original `DashMap<Address, PoolState>`.
+pub struct InMemoryPoolStore {
+    pools: DashMap<Address, PoolState>,
+}
+
+impl InMemoryPoolStore {
+    pub fn new() -> Self {
+        Self { pools: DashMap::new() }
+    }
+}
+
+impl PoolStore for InMemoryPoolStore {
+    fn get(&self, address: &Address) -> Option<PoolState> {
+        self.pools.get(address).map(|p| p.clone())
+    }
+
+    fn update_reserves(&self, address: Address, reserve0: U256, reserve1: U256) -> bool {
+        if let Some(mut pool) = self.pools.get_mut(&address) {
+            pool.reserve0 = reserve0;
+            pool.reserve1 = reserve1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn insert(&self, pool: PoolState) {
+        self.pools.insert(pool.address, pool);
+    }
+
+    fn len(&self) -> usize {
+        self.pools.len()
+    }
+}
+
+// --- mmap bucketed hash table -------------------------------------------------
+
+const SLOTS_PER_BUCKET: usize = 8;
+/// address(20) + reserve0(32) + reserve1(32) + fee(4) + dex_type(1) + last_update(8) + occupied(1), padded
+const SLOT_SIZE: usize = 104;
+const BUCKET_SIZE: usize = SLOT_SIZE * SLOTS_PER_BUCKET;
+const HEADER_SIZE: usize = 64;
+const INITIAL_K: u32 = 16; // 2^16 buckets = 65536 buckets to start
+const MAX_LOAD_FACTOR: f64 = 0.75;
+
+fn bucket_index(address: &Address, num_buckets: u64) -> u64 {
+    let mut hasher = Keccak::v256();
+    let mut out = [0u8; 32];
+    hasher.update(address.as_bytes());
+    hasher.finalize(&mut out);
+    let h = u64::from_le_bytes(out[0..8].try_into().unwrap());
+    h & (num_buckets - 1)
+}
+
+fn write_slot(buf: &mut [u8], pool: &PoolState) {
+    let mut reserve0 = [0u8; 32];
+    let mut reserve1 = [0u8; 32];
+    pool.reserve0.to_little_endian(&mut reserve0);
+    pool.reserve1.to_little_endian(&mut reserve1);
+
+    buf[0] = 1; // occupied
+    buf[1..21].copy_from_slice(pool.address.as_bytes());
+    buf[21..53].copy_from_slice(&reserve0);
+    buf[53..85].copy_from_slice(&reserve1);
+    buf[85..89].copy_from_slice(&pool.fee.to_le_bytes());
+    buf[89] = pool.dex_type as u8;
+    buf[90..98].copy_from_slice(&pool.last_update.to_le_bytes());
+}
+
+fn read_slot(buf: &[u8]) -> Option<PoolState> {
+    if buf[0] == 0 {
+        return None;
+    }
+    let address = Address::from_slice(&buf[1..21]);
+    let reserve0 = U256::from_little_endian(&buf[21..53]);
+    let reserve1 = U256::from_little_endian(&buf[53..85]);
+    let fee = u32::from_le_bytes(buf[85..89].try_into().unwrap());
+    let dex_type = match buf[89] {
+        0 => DexType::UniswapV2,
+        1 => DexType::UniswapV3,
+        2 => DexType::SushiSwap,
+        3 => DexType::Camelot,
+        4 => DexType::Curve,
+        _ => DexType::Balancer,
+    };
+    let last_update = u64::from_le_bytes(buf[90..98].try_into().unwrap());
+
+    Some(PoolState {
+        address,
+        token0: Address::zero(), // not indexed by this slot; looked up via token_to_pools
+        token1: Address::zero(),
+        reserve0,
+        reserve1,
+        fee,
+        dex_type,
+        last_update,
+    })
+}
+
+struct MmapTable {
+    mmap: MmapMut,
+    k: u32, // num_buckets = 2^k
+    occupied: usize,
+}
+
+impl MmapTable {
+    fn num_buckets(&self) -> u64 {
+        1u64 << self.k
+    }
+
+    fn create(path: &Path, k: u32) -> io::Result<Self> {
+        let num_buckets = 1u64 << k;
+        let file_len = HEADER_SIZE as u64 + num_buckets * BUCKET_SIZE as u64;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(file_len)?;
+
+        let mut mmap = unsafe { MmapOptions::new().len(file_len as usize).map_mut(&file)? };
+        mmap[0..4].copy_from_slice(&k.to_le_bytes());
+
+        Ok(Self { mmap, k, occupied: 0 })
+    }
+
+    fn bucket_slots_mut(&mut self, bucket: u64) -> &mut [u8] {
+        let start = HEADER_SIZE + bucket as usize * BUCKET_SIZE;
+        &mut self.mmap[start..start + BUCKET_SIZE]
+    }
+
+    fn bucket_slots(&self, bucket: u64) -> &[u8] {
+        let start = HEADER_SIZE + bucket as usize * BUCKET_SIZE;
+        &self.mmap[start..start + BUCKET_SIZE]
+    }
+
+    fn get(&self, address: &Address) -> Option<PoolState> {
+        let bucket = bucket_index(address, self.num_buckets());
+        let slots = self.bucket_slots(bucket);
+        for i in 0..SLOTS_PER_BUCKET {
+            let slot = &slots[i * SLOT_SIZE..(i + 1) * SLOT_SIZE];
+            if slot[0] == 0 {
+                continue;
+            }
+            if &slot[1..21] == address.as_bytes() {
+                return read_slot(slot);
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if a free slot was found and written, `false` if the
+    /// bucket overflowed (caller must trigger a rehash).
+    fn put(&mut self, pool: &PoolState) -> bool {
+        let bucket = bucket_index(&pool.address, self.num_buckets());
+        let slots = self.bucket_slots_mut(bucket);
+
+        // Overwrite existing entry if present.
+        for i in 0..SLOTS_PER_BUCKET {
+            let slot = &mut slots[i * SLOT_SIZE..(i + 1) * SLOT_SIZE];
+            if slot[0] != 0 && &slot[1..21] == pool.address.as_bytes() {
+                write_slot(slot, pool);
+                return true;
+            }
+        }
+
+        // Linear probe within the bucket for a free slot.
+        for i in 0..SLOTS_PER_BUCKET {
+            let slot = &mut slots[i * SLOT_SIZE..(i + 1) * SLOT_SIZE];
+            if slot[0] == 0 {
+                write_slot(slot, pool);
+                self.occupied += 1;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn load_factor(&self) -> f64 {
+        self.occupied as f64 / (self.num_buckets() as f64 * SLOTS_PER_BUCKET as f64)
+    }
+
+    fn all_pools(&self) -> Vec<PoolState> {
+        let mut out = Vec::with_capacity(self.occupied);
+        for bucket in 0..self.num_buckets() {
+            let slots = self.bucket_slots(bucket);
+            for i in 0..SLOTS_PER_BUCKET {
+                let slot = &slots[i * SLOT_SIZE..(i + 1) * SLOT_SIZE];
+                if let Some(pool) = read_slot(slot) {
+                    out.push(pool);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Bucketed hash table of pool state, memory-mapped from disk.
+///
+/// Keys are located by `index = keccak(addr) & (num_buckets - 1)`, then linearly
+/// probed within the bucket. When a bucket overflows, or global occupancy crosses
+/// `MAX_LOAD_FACTOR`, the table doubles `k` and rehashes into a fresh mmap file.
+pub struct MmapPoolStore {
+    path: PathBuf,
+    table: RwLock<MmapTable>,
+    rehash_count: AtomicUsize,
+}
+
+impl MmapPoolStore {
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let table = MmapTable::create(&path, INITIAL_K)?;
+        Ok(Self {
+            path,
+            table: RwLock::new(table),
+            rehash_count: AtomicUsize::new(0),
+        })
+    }
+
+    fn rehash(&self, table: &mut MmapTable) -> io::Result<()> {
+        let new_k = table.k + 1;
+        info!(
+            "Rehashing mmap pool store: {} -> {} buckets",
+            table.num_buckets(),
+            1u64 << new_k
+        );
+
+        let pools = table.all_pools();
+        let tmp_path = self.path.with_extension("rehash");
+        let mut new_table = MmapTable::create(&tmp_path, new_k)?;
+        for pool in &pools {
+            // A freshly doubled table should never overflow a rehash insert; if it
+            // somehow does, keep doubling rather than silently drop pool state.
+            let mut inserted = new_table.put(pool);
+            while !inserted {
+                let bigger_k = new_table.k + 1;
+                let bigger = MmapTable::create(&tmp_path, bigger_k)?;
+                new_table = bigger;
+                inserted = pools.iter().all(|p| new_table.put(p));
+            }
+        }
+
+        std::fs::rename(&tmp_path, &self.path)?;
+        *table = new_table;
+        self.rehash_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn rehash_count(&self) -> usize {
+        self.rehash_count.load(Ordering::Relaxed)
+    }
+}
+
+impl PoolStore for MmapPoolStore {
+    fn get(&self, address: &Address) -> Option<PoolState> {
+        self.table.read().unwrap().get(address)
+    }
+
+    fn update_reserves(&self, address: Address, reserve0: U256, reserve1: U256) -> bool {
+        let mut table = self.table.write().unwrap();
+        if let Some(mut pool) = table.get(&address) {
+            pool.reserve0 = reserve0;
+            pool.reserve1 = reserve1;
+            table.put(&pool);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn insert(&self, pool: PoolState) {
+        let mut table = self.table.write().unwrap();
+        if !table.put(&pool) || table.load_factor() > MAX_LOAD_FACTOR {
+            self.rehash(&mut table).expect("mmap pool store rehash failed");
+            table.put(&pool);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.table.read().unwrap().occupied
+    }
+}
+
+/// Small in-RAM LRU of the most-traded pools in front of a `PoolStore`, so the
+/// detection hot path only falls through to the (mmap-backed) store on miss.
+pub struct HotPoolCache<S: PoolStore> {
+    backing: Arc<S>,
+    capacity: usize,
+    hot: DashMap<Address, PoolState>,
+    order: RwLock<std::collections::VecDeque<Address>>,
+}
+
+impl<S: PoolStore> HotPoolCache<S> {
+    pub fn new(backing: Arc<S>, capacity: usize) -> Self {
+        Self {
+            backing,
+            capacity,
+            hot: DashMap::new(),
+            order: RwLock::new(std::collections::VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn touch(&self, address: Address) {
+        let mut order = self.order.write().unwrap();
+        order.retain(|a| *a != address);
+        order.push_back(address);
+        while order.len() > self.capacity {
+            if let Some(evicted) = order.pop_front() {
+                self.hot.remove(&evicted);
+            }
+        }
+    }
+
+    pub fn get(&self, address: &Address) -> Option<PoolState> {
+        if let Some(pool) = self.hot.get(address) {
+            let pool = pool.clone();
+            self.touch(*address);
+            return Some(pool);
+        }
+
+        let pool = self.backing.get(address)?;
+        self.hot.insert(*address, pool.clone());
+        self.touch(*address);
+        debug!("Hot cache miss for {:?}, pulled from disk store", address);
+        Some(pool)
+    }
+
+    pub fn update_reserves(&self, address: Address, reserve0: U256, reserve1: U256) -> bool {
+        if let Some(mut pool) = self.hot.get_mut(&address) {
+            pool.reserve0 = reserve0;
+            pool.reserve1 = reserve1;
+        }
+        self.backing.update_reserves(address, reserve0, reserve1)
+    }
+
+    pub fn insert(&self, pool: PoolState) {
+        self.backing.insert(pool.clone());
+        self.hot.insert(pool.address, pool.clone());
+        self.touch(pool.address);
+    }
+
+    pub fn backing(&self) -> &Arc<S> {
+        &self.backing
+    }
+}
@@ -0,0 +1,177 @@
+//! Sandwich detection
+//!
+//! A sandwich frontruns a pending swap by pushing the pool price toward
+//! the victim's `amount_out_min` slippage bound, lets the victim clear at
+//! the worse price, then backruns to close the position out. The two
+//! `Opportunity` entries this produces share the victim's `target_tx` so
+//! the bundle builder can place them immediately either side of it.
+
+use crate::config::Config;
+use crate::detector::arbitrage::{quote_constant_product, u256_to_u128};
+use crate::detector::state_reader::StateReader;
+use crate::detector::swap_parse;
+use crate::types::{Opportunity, OpportunityType, PendingTx};
+use ethers::types::Address;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Sandwich detector for pending swaps
+pub struct SandwichDetector {
+    config: Arc<Config>,
+    state_reader: Option<Arc<dyn StateReader>>,
+}
+
+impl SandwichDetector {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            config,
+            state_reader: None,
+        }
+    }
+
+    /// Read real pool reserves for sizing the frontrun/backrun instead of
+    /// guessing at a fixed impact.
+    pub fn set_state_reader(&mut self, reader: Arc<dyn StateReader>) {
+        self.state_reader = Some(reader);
+    }
+
+    /// Detects a sandwich against `tx`, returning the linked (frontrun,
+    /// backrun) pair if one clears the minimum profit threshold.
+    pub async fn detect(&self, tx: &PendingTx) -> Option<(Opportunity, Opportunity)> {
+        let victim = swap_parse::parse_swap(&tx.input)?;
+        let pool = tx.to.map(Address::from)?;
+        let reader = self.state_reader.as_ref()?;
+        let reserves = reader.read_reserves(pool).await.ok()?;
+        let token_in = Address::from_str(&victim.token_in).ok()?;
+        let token_out = Address::from_str(&victim.token_out).ok()?;
+
+        // `read_reserves` returns (reserve0, reserve1) in sorted-address
+        // order, not swap-direction order; orient to (token_in, token_out)
+        // the same way `arbitrage.rs`'s `reserves_for` does.
+        let (reserve_in, reserve_out) = if token_in < token_out {
+            (u256_to_u128(reserves.reserve0)?, u256_to_u128(reserves.reserve1)?)
+        } else {
+            (u256_to_u128(reserves.reserve1)?, u256_to_u128(reserves.reserve0)?)
+        };
+        let fee_bps = (victim.fee / 100) as u128;
+
+        let (frontrun_amount, frontrun_out, profit) = solve_frontrun(
+            reserve_in,
+            reserve_out,
+            victim.amount_in,
+            victim.amount_out_min,
+            fee_bps,
+        )?;
+
+        let min_profit = self.config.strategy.min_profit_wei;
+        if profit < min_profit {
+            return None;
+        }
+
+        let frontrun = Opportunity {
+            opportunity_type: OpportunityType::Sandwich,
+            token_in: victim.token_in.clone(),
+            token_out: victim.token_out.clone(),
+            amount_in: frontrun_amount,
+            expected_profit: profit,
+            gas_estimate: 150_000,
+            deadline: tx.timestamp + 12,
+            path: vec![victim.dex],
+            target_tx: Some(tx.hash),
+        };
+
+        let backrun = Opportunity {
+            opportunity_type: OpportunityType::Sandwich,
+            token_in: victim.token_out,
+            token_out: victim.token_in,
+            amount_in: frontrun_out,
+            expected_profit: profit,
+            gas_estimate: 150_000,
+            deadline: tx.timestamp + 12,
+            path: vec![victim.dex],
+            target_tx: Some(tx.hash),
+        };
+
+        Some((frontrun, backrun))
+    }
+}
+
+/// Binary-searches the largest frontrun input `x` in `[0, reserve_in]` for
+/// which the victim's swap still clears `victim_min_out` once it executes
+/// against the reserves the frontrun leaves behind — the victim's output is
+/// monotonically non-increasing in `x`, so the feasible region is a prefix
+/// of `[0, reserve_in]` and its upper bound is exactly the profit-maximizing
+/// `x` (more frontrun size always extracts more value up to the point the
+/// victim would no longer clear its own slippage bound).
+///
+/// Returns `(x, frontrun_amount_out, attacker_profit)`.
+fn solve_frontrun(
+    reserve_in: u128,
+    reserve_out: u128,
+    victim_amount_in: u128,
+    victim_min_out: u128,
+    fee_bps: u128,
+) -> Option<(u128, u128, u128)> {
+    let victim_clears = |x: u128| -> bool {
+        let Some(front_out) = quote_constant_product(x, reserve_in, reserve_out, fee_bps) else {
+            return x == 0;
+        };
+        let Some(reserve_in_after) = reserve_in.checked_add(x) else {
+            return false;
+        };
+        let Some(reserve_out_after) = reserve_out.checked_sub(front_out) else {
+            return false;
+        };
+        let victim_out = quote_constant_product(
+            victim_amount_in,
+            reserve_in_after,
+            reserve_out_after,
+            fee_bps,
+        )
+        .unwrap_or(0);
+        victim_out >= victim_min_out
+    };
+
+    let mut lo = 0u128;
+    let mut hi = reserve_in;
+    while lo < hi {
+        let mid = lo + (hi - lo + 1) / 2;
+        if victim_clears(mid) {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    let x = lo;
+    if x == 0 {
+        return None;
+    }
+
+    let frontrun_out = quote_constant_product(x, reserve_in, reserve_out, fee_bps)?;
+    let reserve_in_after_front = reserve_in.checked_add(x)?;
+    let reserve_out_after_front = reserve_out.checked_sub(frontrun_out)?;
+
+    let victim_out = quote_constant_product(
+        victim_amount_in,
+        reserve_in_after_front,
+        reserve_out_after_front,
+        fee_bps,
+    )?;
+    let reserve_in_after_victim = reserve_in_after_front.checked_add(victim_amount_in)?;
+    let reserve_out_after_victim = reserve_out_after_front.checked_sub(victim_out)?;
+
+    // Backrun sells `frontrun_out` of the output token back for the input
+    // token, against the reserves the victim's swap just moved.
+    let backrun_out = quote_constant_product(
+        frontrun_out,
+        reserve_out_after_victim,
+        reserve_in_after_victim,
+        fee_bps,
+    )?;
+
+    let gas_cost = 2 * 150_000u128 * 50_000_000_000u128;
+    let profit = backrun_out.checked_sub(x)?.checked_sub(gas_cost)?;
+
+    Some((x, frontrun_out, profit))
+}
@@ -0,0 +1,182 @@
+//! Pluggable pool-state reads
+//!
+//! `BackrunDetector::estimate_price_impact` and the arbitrage detector's price
+//! lookups used to be hard-coded ("0.1% impact per 10 ETH", a fixed price
+//! table) because there was no abstraction for asking "what are this pool's
+//! reserves right now?" without hard-wiring an RPC client. `StateReader`
+//! applies the same trick as [`super::pool_store::PoolStore`] to live chain
+//! state: detectors take an `Arc<dyn StateReader>` and the same detection
+//! code runs unchanged against a live node, a warm in-memory cache, or (for
+//! the simulator) a forked snapshot.
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use ethers::providers::Middleware;
+use ethers::types::{Address, Eip1559TransactionRequest, U256};
+
+/// A pool's raw reserves, token-order matching `token0`/`token1` on-chain.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Reserves {
+    pub reserve0: U256,
+    pub reserve1: U256,
+}
+
+/// Uniswap V3-style `slot0` state.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Slot0 {
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub liquidity: u128,
+}
+
+/// Backend-agnostic read access to on-chain pool/contract state.
+#[async_trait]
+pub trait StateReader: Send + Sync {
+    async fn read_reserves(&self, pool: Address) -> anyhow::Result<Reserves>;
+    async fn read_slot0(&self, pool: Address) -> anyhow::Result<Slot0>;
+    async fn read_storage(&self, addr: Address, key: U256) -> anyhow::Result<U256>;
+}
+
+/// `getReserves()` selector: `keccak256("getReserves()")[0..4]`.
+const GET_RESERVES_SELECTOR: [u8; 4] = [0x09, 0x02, 0xf1, 0xac];
+/// `slot0()` selector: `keccak256("slot0()")[0..4]`.
+const SLOT0_SELECTOR: [u8; 4] = [0x38, 0x50, 0xc7, 0xbd];
+/// `liquidity()` selector: `keccak256("liquidity()")[0..4]`. `slot0()` alone
+/// doesn't carry the pool's in-range liquidity, so this is a second call.
+const LIQUIDITY_SELECTOR: [u8; 4] = [0x1a, 0x68, 0x65, 0x02];
+
+/// Reads live state over JSON-RPC via any `ethers` `Middleware` (HTTP, WS, ...).
+pub struct RpcStateReader<M> {
+    client: M,
+}
+
+impl<M: Middleware + 'static> RpcStateReader<M> {
+    pub fn new(client: M) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware + 'static> StateReader for RpcStateReader<M> {
+    async fn read_reserves(&self, pool: Address) -> anyhow::Result<Reserves> {
+        let tx = Eip1559TransactionRequest::new().to(pool).data(GET_RESERVES_SELECTOR.to_vec());
+        let out = self.client.call(&tx.into(), None).await
+            .map_err(|e| anyhow::anyhow!("getReserves({:?}) call failed: {}", pool, e))?;
+
+        if out.len() < 64 {
+            anyhow::bail!("getReserves({:?}) returned {} bytes, expected >= 64", pool, out.len());
+        }
+
+        Ok(Reserves {
+            reserve0: U256::from_big_endian(&out[0..32]),
+            reserve1: U256::from_big_endian(&out[32..64]),
+        })
+    }
+
+    async fn read_slot0(&self, pool: Address) -> anyhow::Result<Slot0> {
+        let tx = Eip1559TransactionRequest::new().to(pool).data(SLOT0_SELECTOR.to_vec());
+        let out = self.client.call(&tx.into(), None).await
+            .map_err(|e| anyhow::anyhow!("slot0({:?}) call failed: {}", pool, e))?;
+
+        if out.len() < 64 {
+            anyhow::bail!("slot0({:?}) returned {} bytes, expected >= 64", pool, out.len());
+        }
+
+        // `slot0()` doesn't carry liquidity — a separate `liquidity()` call
+        // is required (see `arbitrum/pools.rs`'s `encode_liquidity`).
+        let liquidity_tx = Eip1559TransactionRequest::new().to(pool).data(LIQUIDITY_SELECTOR.to_vec());
+        let liquidity_out = self.client.call(&liquidity_tx.into(), None).await
+            .map_err(|e| anyhow::anyhow!("liquidity({:?}) call failed: {}", pool, e))?;
+
+        if liquidity_out.len() < 32 {
+            anyhow::bail!("liquidity({:?}) returned {} bytes, expected >= 32", pool, liquidity_out.len());
+        }
+
+        Ok(Slot0 {
+            sqrt_price_x96: U256::from_big_endian(&out[0..32]),
+            tick: U256::from_big_endian(&out[32..64]).low_u32() as i32,
+            liquidity: U256::from_big_endian(&liquidity_out[0..32]).low_u128(),
+        })
+    }
+
+    async fn read_storage(&self, addr: Address, key: U256) -> anyhow::Result<U256> {
+        let mut key_bytes = [0u8; 32];
+        key.to_big_endian(&mut key_bytes);
+        let value = self.client.get_storage_at(addr, key_bytes.into(), None).await
+            .map_err(|e| anyhow::anyhow!("get_storage_at({:?}, {}) failed: {}", addr, key, e))?;
+        Ok(U256::from_big_endian(value.as_bytes()))
+    }
+}
+
+/// In-memory fixture/cache backend — pre-populated for tests, or kept warm
+/// in front of an `RpcStateReader` by callers that poll reserves themselves.
+#[derive(Default)]
+pub struct CachedStateReader {
+    reserves: DashMap<Address, Reserves>,
+    slot0: DashMap<Address, Slot0>,
+    storage: DashMap<(Address, U256), U256>,
+}
+
+impl CachedStateReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_reserves(&self, pool: Address, reserves: Reserves) {
+        self.reserves.insert(pool, reserves);
+    }
+
+    pub fn set_slot0(&self, pool: Address, slot0: Slot0) {
+        self.slot0.insert(pool, slot0);
+    }
+
+    pub fn set_storage(&self, addr: Address, key: U256, value: U256) {
+        self.storage.insert((addr, key), value);
+    }
+}
+
+#[async_trait]
+impl StateReader for CachedStateReader {
+    async fn read_reserves(&self, pool: Address) -> anyhow::Result<Reserves> {
+        self.reserves.get(&pool).map(|r| *r)
+            .ok_or_else(|| anyhow::anyhow!("no cached reserves for pool {:?}", pool))
+    }
+
+    async fn read_slot0(&self, pool: Address) -> anyhow::Result<Slot0> {
+        self.slot0.get(&pool).map(|s| *s)
+            .ok_or_else(|| anyhow::anyhow!("no cached slot0 for pool {:?}", pool))
+    }
+
+    async fn read_storage(&self, addr: Address, key: U256) -> anyhow::Result<U256> {
+        self.storage.get(&(addr, key)).map(|v| *v)
+            .ok_or_else(|| anyhow::anyhow!("no cached storage for {:?}:{}", addr, key))
+    }
+}
+
+/// REVM-side reader backed by a snapshot the caller populates up front. It
+/// doesn't read through `EvmSimulator`'s `ForkDb`, which fetches its own
+/// forked state from the provider.
+pub struct RevmStateReader {
+    snapshot: CachedStateReader,
+}
+
+impl RevmStateReader {
+    pub fn from_snapshot(snapshot: CachedStateReader) -> Self {
+        Self { snapshot }
+    }
+}
+
+#[async_trait]
+impl StateReader for RevmStateReader {
+    async fn read_reserves(&self, pool: Address) -> anyhow::Result<Reserves> {
+        self.snapshot.read_reserves(pool).await
+    }
+
+    async fn read_slot0(&self, pool: Address) -> anyhow::Result<Slot0> {
+        self.snapshot.read_slot0(pool).await
+    }
+
+    async fn read_storage(&self, addr: Address, key: U256) -> anyhow::Result<U256> {
+        self.snapshot.read_storage(addr, key).await
+    }
+}
@@ -0,0 +1,157 @@
+//! Shared swap-calldata decoding
+//!
+//! `ArbitrageDetector` and `SandwichDetector` both need to know what a
+//! pending transaction is actually swapping before they can do anything
+//! else with it; this used to live as private methods on
+//! `ArbitrageDetector` alone, but the sandwich strategy needs the exact
+//! same decode, so it's pulled out here rather than copied.
+
+use crate::types::{DexType, SwapInfo};
+use ethers::types::U256;
+
+/// Decodes a swap transaction's calldata into `SwapInfo`, keyed on the
+/// matched function selector. Returns `None` for anything unrecognized.
+pub fn parse_swap(data: &[u8]) -> Option<SwapInfo> {
+    if data.len() < 4 {
+        return None;
+    }
+
+    let selector = &data[0..4];
+
+    match selector {
+        // swapExactTokensForTokens (UniswapV2)
+        [0x38, 0xed, 0x17, 0x39] => parse_v2_swap(data),
+        // exactInputSingle (UniswapV3)
+        [0x41, 0x4b, 0xf3, 0x89] => parse_v3_swap(data),
+        _ => None,
+    }
+}
+
+fn parse_v2_swap(data: &[u8]) -> Option<SwapInfo> {
+    // swapExactTokensForTokens(amountIn, amountOutMin, path, to, deadline) —
+    // 5 fixed-size head words (amountIn, amountOutMin, path offset, to,
+    // deadline) before the dynamic `path` array. Each head word is a
+    // right-aligned 32-byte ABI word, so a uint128/address value lives in
+    // the word's low 16/20 bytes, not its first 16/20.
+    if data.len() < 4 + 5 * 32 {
+        return None;
+    }
+    let args = &data[4..];
+
+    let amount_in = u128::from_be_bytes(args[16..32].try_into().ok()?);
+    let amount_out_min = u128::from_be_bytes(args[48..64].try_into().ok()?);
+    let path_offset = word_to_len(&args[64..96])?;
+
+    if args.len() < path_offset + 32 {
+        return None;
+    }
+    let path_len = word_to_len(&args[path_offset..path_offset + 32])?;
+    if path_len < 2 || args.len() < path_offset + 32 + path_len * 32 {
+        return None;
+    }
+
+    // Path is dynamic; first address is token_in, last is token_out.
+    let first = path_offset + 32;
+    let last = path_offset + 32 + (path_len - 1) * 32;
+    let token_in = format!("0x{}", hex::encode(&args[first + 12..first + 32]));
+    let token_out = format!("0x{}", hex::encode(&args[last + 12..last + 32]));
+
+    Some(SwapInfo {
+        dex: DexType::UniswapV2,
+        token_in,
+        token_out,
+        amount_in,
+        amount_out_min,
+        fee: 3000, // 0.3%
+    })
+}
+
+/// Decode a 32-byte ABI word as a length/offset, rejecting anything absurdly
+/// large — calldata comes straight off the mempool, so a malicious value
+/// here shouldn't be trusted to index into `data` without a bounds check.
+fn word_to_len(word: &[u8]) -> Option<usize> {
+    let value = U256::from_big_endian(word);
+    if value > U256::from(u32::MAX) {
+        return None;
+    }
+    Some(value.as_u32() as usize)
+}
+
+fn parse_v3_swap(data: &[u8]) -> Option<SwapInfo> {
+    // ExactInputSingleParams: tokenIn, tokenOut, fee, recipient,
+    // deadline, amountIn, amountOutMinimum, sqrtPriceLimitX96 — 8
+    // fixed-size words, inlined with no offset pointer.
+    if data.len() < 4 + 8 * 32 {
+        return None;
+    }
+    let args = &data[4..];
+
+    let token_in = format!("0x{}", hex::encode(&args[12..32]));
+    let token_out = format!("0x{}", hex::encode(&args[44..64]));
+    // uint24 fee, low 3 bytes of the third word (500 / 3000 / 10000).
+    let fee = u32::from_be_bytes([0, args[93], args[94], args[95]]);
+    let amount_in = u128::from_be_bytes(args[176..192].try_into().ok()?);
+    let amount_out_min = u128::from_be_bytes(args[208..224].try_into().ok()?);
+
+    Some(SwapInfo {
+        dex: DexType::UniswapV3,
+        token_in,
+        token_out,
+        amount_in,
+        amount_out_min,
+        fee,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u256_word(value: u128) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[16..32].copy_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    fn address_word(addr: &[u8; 20]) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[12..32].copy_from_slice(addr);
+        word
+    }
+
+    /// Real `swapExactTokensForTokens` calldata, laid out exactly like
+    /// `replay.rs`'s `build_v2_calldata` (path offset 160, 2-hop path).
+    fn build_v2_calldata(token_in: &[u8; 20], token_out: &[u8; 20], amount_in: u128) -> Vec<u8> {
+        let amount_out_min = amount_in / 100;
+        let mut data = vec![0x38, 0xed, 0x17, 0x39];
+        data.extend_from_slice(&u256_word(amount_in));
+        data.extend_from_slice(&u256_word(amount_out_min));
+        data.extend_from_slice(&u256_word(160)); // path offset: 5 head words * 32
+        data.extend_from_slice(&address_word(&[0u8; 20])); // to
+        data.extend_from_slice(&u256_word(u64::MAX as u128)); // deadline
+        data.extend_from_slice(&u256_word(2)); // path.length
+        data.extend_from_slice(&address_word(token_in));
+        data.extend_from_slice(&address_word(token_out));
+        data
+    }
+
+    #[test]
+    fn parse_v2_swap_decodes_real_calldata() {
+        let token_in = [0x11u8; 20];
+        let token_out = [0x22u8; 20];
+        let data = build_v2_calldata(&token_in, &token_out, 5_000_000_000_000_000_000u128);
+
+        let swap = parse_swap(&data).expect("should decode a well-formed V2 swap");
+        assert_eq!(swap.dex, DexType::UniswapV2);
+        assert_eq!(swap.amount_in, 5_000_000_000_000_000_000u128);
+        assert_eq!(swap.amount_out_min, 50_000_000_000_000_000u128);
+        assert_eq!(swap.token_in, format!("0x{}", hex::encode(token_in)));
+        assert_eq!(swap.token_out, format!("0x{}", hex::encode(token_out)));
+    }
+
+    #[test]
+    fn parse_v2_swap_rejects_truncated_calldata() {
+        let data = vec![0x38, 0xed, 0x17, 0x39, 0, 0];
+        assert!(parse_swap(&data).is_none());
+    }
+}
@@ -17,8 +17,26 @@ extern "C" {
     
     // Calldata parsing
     pub fn mev_parse_swap(calldata: *const u8, len: usize, info: *mut SwapInfoFFI) -> i32;
+    // Same parse, but writes amounts as native-order [u64;4] limbs straight into
+    // `SwapInfoLE` instead of big-endian bytes, so the hot path never byte-swaps.
+    pub fn mev_parse_swap_le(calldata: *const u8, len: usize, info: *mut SwapInfoLE) -> i32;
     pub fn mev_get_selector(calldata: *const u8, out: *mut u8);
-    
+
+    // ECDSA recovery (C counterpart; current sender-recovery path uses the
+    // `secp256k1` Rust crate directly since correctness here is safety-critical)
+    pub fn mev_ecrecover(
+        msg_hash: *const u8,
+        sig_r: *const u8,
+        sig_s: *const u8,
+        rec_id: i32,
+        out_address: *mut u8,
+    ) -> i32;
+
+    // RLP decoding (C counterpart; `safe::decode_transaction` uses the Rust item
+    // walker for now since calldata is variable-length and awkward to hand back
+    // across the FFI boundary without an extra copy)
+    pub fn mev_rlp_decode_tx(raw: *const u8, len: usize, out_data_offset: *mut usize, out_data_len: *mut usize) -> i32;
+
     // SIMD utils
     pub fn mev_memcmp_fast(a: *const u8, b: *const u8, len: usize) -> i32;
     pub fn mev_address_eq(a: *const u8, b: *const u8) -> i32;
@@ -28,6 +46,15 @@ extern "C" {
         amount_in: u64,
         outputs: *mut u64,
     );
+    // Full 256-bit variant: reserves/amount/outputs are flattened [u64;4] limb
+    // arrays (4 pools * 4 limbs each) so large-balance pools no longer get
+    // silently truncated to u64, while keeping the 4-wide SIMD batching.
+    pub fn mev_calc_price_impact_batch_u256(
+        reserves0: *const u64,
+        reserves1: *const u64,
+        amount_in: *const u64,
+        outputs: *mut u64,
+    );
     pub fn mev_rdtsc() -> u64;
     pub fn mev_prefetch_pool(data: *const c_void);
     
@@ -72,6 +99,36 @@ impl Default for SwapInfoFFI {
     }
 }
 
+/// Same layout as `SwapInfoFFI`, but amounts are native-order `[u64;4]` limbs
+/// (least-significant limb first) instead of big-endian bytes — the
+/// representation an EVM keeps values in on its stack. Byte-reversal only
+/// happens when a value actually needs to leave this form (e.g. RLP-encoding
+/// calldata), not on every parsed swap.
+#[repr(C)]
+pub struct SwapInfoLE {
+    pub dex_type: u8,
+    pub token_in: [u8; 20],
+    pub token_out: [u8; 20],
+    pub amount_in: [u64; 4],
+    pub amount_out_min: [u64; 4],
+    pub pool_address: [u8; 20],
+    pub fee: u32,
+}
+
+impl Default for SwapInfoLE {
+    fn default() -> Self {
+        Self {
+            dex_type: 0,
+            token_in: [0u8; 20],
+            token_out: [0u8; 20],
+            amount_in: [0u64; 4],
+            amount_out_min: [0u64; 4],
+            pool_address: [0u8; 20],
+            fee: 0,
+        }
+    }
+}
+
 /// High-level Rust wrappers with safety
 pub mod safe {
     use super::*;
@@ -131,6 +188,108 @@ pub mod safe {
         outputs
     }
     
+    /// Construct a `U256` from four native-order 64-bit limbs (least-significant
+    /// first) — the representation an EVM keeps values in on its stack. Cheaper
+    /// than `U256::from_big_endian`, which has to reverse the byte order.
+    #[inline(always)]
+    pub fn u256_from_limbs(limbs: [u64; 4]) -> U256 {
+        let mut bytes = [0u8; 32];
+        for i in 0..4 {
+            bytes[i * 8..i * 8 + 8].copy_from_slice(&limbs[i].to_le_bytes());
+        }
+        U256::from_little_endian(&bytes)
+    }
+
+    /// Decompose a `U256` into four native-order 64-bit limbs (least-significant
+    /// first). Only needed at the ABI boundary, e.g. just before RLP-encoding.
+    #[inline(always)]
+    pub fn u256_to_limbs(value: U256) -> [u64; 4] {
+        let mut bytes = [0u8; 32];
+        value.to_little_endian(&mut bytes);
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            limbs[i] = u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap());
+        }
+        limbs
+    }
+
+    /// Batch price impact calculation (4 pools at once), full 256-bit reserves.
+    /// Prefer this over `calc_price_impact_batch` once a pool's balance can
+    /// exceed `u64::MAX` (e.g. low-decimal tokens or concentrated liquidity) —
+    /// the `u64` path silently truncates those.
+    ///
+    /// Not yet called outside this module: nothing in `core/src` needs
+    /// reserves beyond `u64::MAX` today, and this (like `parse_swap_le`)
+    /// calls into the `mev_fast` C library, which this tree can't build or
+    /// exercise in a test. `u256_from_limbs`/`u256_to_limbs` above are pure
+    /// Rust and are covered by round-trip tests regardless.
+    pub fn calc_price_impact_batch_u256(
+        reserves0: &[U256; 4],
+        reserves1: &[U256; 4],
+        amount_in: U256,
+    ) -> [U256; 4] {
+        let flatten = |values: &[U256; 4]| -> [u64; 16] {
+            let mut out = [0u64; 16];
+            for (i, v) in values.iter().enumerate() {
+                out[i * 4..i * 4 + 4].copy_from_slice(&u256_to_limbs(*v));
+            }
+            out
+        };
+
+        let r0 = flatten(reserves0);
+        let r1 = flatten(reserves1);
+        let amt = u256_to_limbs(amount_in);
+        let mut raw_out = [0u64; 16];
+        unsafe {
+            mev_calc_price_impact_batch_u256(
+                r0.as_ptr(),
+                r1.as_ptr(),
+                amt.as_ptr(),
+                raw_out.as_mut_ptr(),
+            );
+        }
+
+        let mut outputs = [U256::zero(); 4];
+        for i in 0..4 {
+            let limbs: [u64; 4] = raw_out[i * 4..i * 4 + 4].try_into().unwrap();
+            outputs[i] = u256_from_limbs(limbs);
+        }
+        outputs
+    }
+
+    /// Parse swap from calldata, keeping amounts as native limbs — skips the
+    /// big-endian byte-reversal `parse_swap` pays on every call.
+    ///
+    /// Not yet called outside this module, for the same reason as
+    /// `calc_price_impact_batch_u256` above: it's a `mev_fast`-backed
+    /// alternative to an existing path, waiting on a caller that actually
+    /// needs the extra speed.
+    pub fn parse_swap_le(calldata: &[u8]) -> Option<SwapInfo> {
+        let mut info = SwapInfoLE::default();
+        let result = unsafe {
+            mev_parse_swap_le(calldata.as_ptr(), calldata.len(), &mut info)
+        };
+
+        if result != 0 {
+            return None;
+        }
+
+        Some(SwapInfo {
+            dex_type: match info.dex_type {
+                1 => DexType::UniswapV2,
+                2 => DexType::UniswapV3,
+                3 => DexType::SushiSwap,
+                _ => return None,
+            },
+            token_in: Address::from_slice(&info.token_in),
+            token_out: Address::from_slice(&info.token_out),
+            amount_in: u256_from_limbs(info.amount_in),
+            amount_out_min: u256_from_limbs(info.amount_out_min),
+            pool_address: Address::from_slice(&info.pool_address),
+            fee: info.fee,
+        })
+    }
+
     /// Parse swap from calldata
     pub fn parse_swap(calldata: &[u8]) -> Option<SwapInfo> {
         let mut info = SwapInfoFFI::default();
@@ -158,6 +317,302 @@ pub mod safe {
         })
     }
     
+    /// Recover the sender of a raw (signed) mempool transaction.
+    ///
+    /// Detects the envelope from the first byte (`0x02`/`0x01` = typed EIP-1559/2930,
+    /// otherwise legacy), rebuilds the unsigned signing payload by RLP-re-encoding the
+    /// transaction fields without `v`/`r`/`s`, and recovers the signer's address via
+    /// `secp256k1`. Returns `None` on malformed input or recovery failure.
+    pub fn recover_sender(raw_tx: &[u8]) -> Option<Address> {
+        match raw_tx.first()? {
+            0x02 => recover_typed_sender(&raw_tx[1..], 0x02),
+            0x01 => recover_typed_sender(&raw_tx[1..], 0x01),
+            _ => recover_legacy_sender(raw_tx),
+        }
+    }
+
+    fn recover_legacy_sender(raw_tx: &[u8]) -> Option<Address> {
+        let items = rlp_top_level_items(raw_tx)?;
+        if items.len() != 9 {
+            return None;
+        }
+
+        let v = rlp_item_to_u64(items[6])?;
+        let r = rlp_item_payload(items[7])?;
+        let s = rlp_item_payload(items[8])?;
+
+        let (rec_id, unsigned_items): (u8, Vec<Vec<u8>>) = if v >= 35 {
+            // EIP-155: recId = v - 35 - 2*chainId
+            let chain_id = (v - 35) / 2;
+            let rec_id = (v - 35 - 2 * chain_id) as u8;
+            let unsigned = vec![
+                items[0].to_vec(),
+                items[1].to_vec(),
+                items[2].to_vec(),
+                items[3].to_vec(),
+                items[4].to_vec(),
+                items[5].to_vec(),
+                rlp_encode_string(&trim_be(chain_id.to_be_bytes().to_vec())),
+                rlp_encode_string(&[]),
+                rlp_encode_string(&[]),
+            ];
+            (rec_id, unsigned)
+        } else {
+            // Pre-EIP-155 (Homestead): recId = v - 27, no chainId replay protection
+            let rec_id = v.checked_sub(27)? as u8;
+            let unsigned = items[0..6].iter().map(|s| s.to_vec()).collect();
+            (rec_id, unsigned)
+        };
+
+        let unsigned_rlp = rlp_wrap_list_of_raw(&unsigned_items);
+        let msg_hash = keccak256_fast(&unsigned_rlp);
+        recover_address(msg_hash.as_bytes(), r, s, rec_id)
+    }
+
+    /// `tx_type` is `0x01` (EIP-2930) or `0x02` (EIP-1559). Both share the shape
+    /// `[..unsigned fields.., accessList, yParity, r, s]`; only the unsigned
+    /// field count before `accessList` differs (`chainId,nonce,gasPrice,gasLimit,
+    /// to,value,data` for 2930 vs the same with `maxPriorityFee`/`maxFee` for 1559).
+    fn recover_typed_sender(payload: &[u8], tx_type: u8) -> Option<Address> {
+        let items = rlp_top_level_items(payload)?;
+        let expected_len = if tx_type == 0x02 { 12 } else { 11 };
+        if items.len() != expected_len {
+            return None;
+        }
+
+        let unsigned_field_count = expected_len - 3; // drop yParity, r, s
+        let y_parity = rlp_item_to_u64(items[unsigned_field_count])?;
+        let r = rlp_item_payload(items[unsigned_field_count + 1])?;
+        let s = rlp_item_payload(items[unsigned_field_count + 2])?;
+
+        let mut unsigned_rlp = vec![tx_type];
+        unsigned_rlp.extend_from_slice(&rlp_wrap_list_of_raw(
+            &items[..unsigned_field_count].iter().map(|s| s.to_vec()).collect::<Vec<_>>(),
+        ));
+
+        let msg_hash = keccak256_fast(&unsigned_rlp);
+        recover_address(msg_hash.as_bytes(), r, s, y_parity as u8)
+    }
+
+    fn recover_address(msg_hash: &[u8], r: &[u8], s: &[u8], rec_id: u8) -> Option<Address> {
+        use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+        use secp256k1::{Message, Secp256k1};
+
+        if r.len() > 32 || s.len() > 32 {
+            return None;
+        }
+
+        let mut sig = [0u8; 64];
+        sig[32 - r.len()..32].copy_from_slice(r);
+        sig[64 - s.len()..64].copy_from_slice(s);
+
+        let recovery_id = RecoveryId::from_i32(rec_id as i32).ok()?;
+        let signature = RecoverableSignature::from_compact(&sig, recovery_id).ok()?;
+        let message = Message::from_digest_slice(msg_hash).ok()?;
+
+        let secp = Secp256k1::verification_only();
+        let pubkey = secp.recover_ecdsa(&message, &signature).ok()?;
+        let pubkey_bytes = pubkey.serialize_uncompressed();
+
+        // Address = low 20 bytes of keccak256(uncompressed pubkey, sans the 0x04 prefix)
+        let hash = keccak256_fast(&pubkey_bytes[1..]);
+        Some(Address::from_slice(&hash.as_bytes()[12..]))
+    }
+
+    /// Transaction fields extracted from a raw envelope, borrowing `data`
+    /// straight out of the input buffer (no calldata copy).
+    #[derive(Debug, Clone)]
+    pub struct DecodedTx<'a> {
+        pub to: Option<Address>,
+        pub value: U256,
+        pub gas_limit: u64,
+        pub max_fee_per_gas: U256,
+        pub data: &'a [u8],
+    }
+
+    /// Strip the envelope from a raw transaction and pull out `to`, `value`,
+    /// `gas_limit`, `max_fee_per_gas`, and the calldata slice. Handles legacy
+    /// and typed (`0x01`/`0x02`) envelopes; feed `data` straight into
+    /// `parse_swap` for a `decode_transaction(raw).and_then(|tx| parse_swap(tx.data))`
+    /// ingestion pipeline. Returns `None` on malformed input.
+    pub fn decode_transaction(raw: &[u8]) -> Option<DecodedTx<'_>> {
+        match raw.first()? {
+            0x02 => decode_eip1559(&raw[1..]),
+            0x01 => decode_eip2930(&raw[1..]),
+            _ => decode_legacy(raw),
+        }
+    }
+
+    fn decode_legacy(raw: &[u8]) -> Option<DecodedTx<'_>> {
+        let items = rlp_top_level_items(raw)?;
+        if items.len() != 9 {
+            return None;
+        }
+        Some(DecodedTx {
+            max_fee_per_gas: rlp_item_to_u256(items[1])?,
+            gas_limit: rlp_item_to_u64(items[2])?,
+            to: decode_optional_address(items[3])?,
+            value: rlp_item_to_u256(items[4])?,
+            data: rlp_item_payload(items[5])?,
+        })
+    }
+
+    /// EIP-2930 (type `0x01`): `[chainId, nonce, gasPrice, gasLimit, to, value, data, accessList, yParity, r, s]`.
+    fn decode_eip2930(payload: &[u8]) -> Option<DecodedTx<'_>> {
+        let items = rlp_top_level_items(payload)?;
+        if items.len() != 11 {
+            return None;
+        }
+        Some(DecodedTx {
+            max_fee_per_gas: rlp_item_to_u256(items[2])?,
+            gas_limit: rlp_item_to_u64(items[3])?,
+            to: decode_optional_address(items[4])?,
+            value: rlp_item_to_u256(items[5])?,
+            data: rlp_item_payload(items[6])?,
+        })
+    }
+
+    /// EIP-1559 (type `0x02`): `[chainId, nonce, maxPriorityFeePerGas, maxFeePerGas,
+    /// gasLimit, to, value, data, accessList, yParity, r, s]`.
+    fn decode_eip1559(payload: &[u8]) -> Option<DecodedTx<'_>> {
+        let items = rlp_top_level_items(payload)?;
+        if items.len() != 12 {
+            return None;
+        }
+        Some(DecodedTx {
+            max_fee_per_gas: rlp_item_to_u256(items[3])?,
+            gas_limit: rlp_item_to_u64(items[4])?,
+            to: decode_optional_address(items[5])?,
+            value: rlp_item_to_u256(items[6])?,
+            data: rlp_item_payload(items[7])?,
+        })
+    }
+
+    /// `to` is the empty string for contract-creation transactions.
+    fn decode_optional_address(item: &[u8]) -> Option<Option<Address>> {
+        let payload = rlp_item_payload(item)?;
+        if payload.is_empty() {
+            Some(None)
+        } else if payload.len() == 20 {
+            Some(Some(Address::from_slice(payload)))
+        } else {
+            None
+        }
+    }
+
+    fn rlp_item_to_u256(item: &[u8]) -> Option<U256> {
+        let payload = rlp_item_payload(item)?;
+        if payload.len() > 32 {
+            return None;
+        }
+        Some(U256::from_big_endian(payload))
+    }
+
+    /// Strip a transaction's top-level RLP list header and return each field's
+    /// raw (still fully RLP-encoded) byte span, without recursing into nested
+    /// lists like `accessList` — callers only need field boundaries, not their
+    /// contents, to rebuild the unsigned signing payload.
+    fn rlp_top_level_items(data: &[u8]) -> Option<Vec<&[u8]>> {
+        let (is_list, payload_start, payload_len) = rlp_header(data)?;
+        if !is_list || payload_start + payload_len > data.len() {
+            return None;
+        }
+
+        let mut items = Vec::new();
+        let mut pos = payload_start;
+        let end = payload_start + payload_len;
+        while pos < end {
+            let (_, item_payload_start, item_payload_len) = rlp_header(&data[pos..])?;
+            let item_len = (item_payload_start - 0) + item_payload_len;
+            let item_total_len = item_payload_start + item_payload_len;
+            if pos + item_total_len > end {
+                return None;
+            }
+            items.push(&data[pos..pos + item_total_len]);
+            pos += item_total_len;
+            let _ = item_len;
+        }
+
+        Some(items)
+    }
+
+    /// Parse one RLP header, returning `(is_list, payload_start, payload_len)`
+    /// where `payload_start` is relative to the start of `data`.
+    fn rlp_header(data: &[u8]) -> Option<(bool, usize, usize)> {
+        let first = *data.first()?;
+        match first {
+            0x00..=0x7f => Some((false, 0, 1)),
+            0x80..=0xb7 => Some((false, 1, (first - 0x80) as usize)),
+            0xb8..=0xbf => {
+                let len_of_len = (first - 0xb7) as usize;
+                let len = be_bytes_to_usize(data.get(1..1 + len_of_len)?);
+                Some((false, 1 + len_of_len, len))
+            }
+            0xc0..=0xf7 => Some((true, 1, (first - 0xc0) as usize)),
+            0xf8..=0xff => {
+                let len_of_len = (first - 0xf7) as usize;
+                let len = be_bytes_to_usize(data.get(1..1 + len_of_len)?);
+                Some((true, 1 + len_of_len, len))
+            }
+        }
+    }
+
+    fn be_bytes_to_usize(bytes: &[u8]) -> usize {
+        bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize)
+    }
+
+    /// Payload bytes of a single RLP-encoded item (strips its own header).
+    fn rlp_item_payload(item: &[u8]) -> Option<&[u8]> {
+        let (_, start, len) = rlp_header(item)?;
+        item.get(start..start + len)
+    }
+
+    fn rlp_item_to_u64(item: &[u8]) -> Option<u64> {
+        let payload = rlp_item_payload(item)?;
+        if payload.len() > 8 {
+            return None;
+        }
+        Some(payload.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64))
+    }
+
+    fn trim_be(bytes: Vec<u8>) -> Vec<u8> {
+        let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+        bytes[start..].to_vec()
+    }
+
+    /// RLP-encode a byte string value.
+    pub fn rlp_encode_string(data: &[u8]) -> Vec<u8> {
+        if data.len() == 1 && data[0] < 0x80 {
+            data.to_vec()
+        } else if data.len() < 56 {
+            let mut out = vec![0x80 + data.len() as u8];
+            out.extend_from_slice(data);
+            out
+        } else {
+            let len_bytes = trim_be(data.len().to_be_bytes().to_vec());
+            let mut out = vec![0xb7 + len_bytes.len() as u8];
+            out.extend_from_slice(&len_bytes);
+            out.extend_from_slice(data);
+            out
+        }
+    }
+
+    /// Wrap already-RLP-encoded items (each a full header+payload span) in a list header.
+    pub fn rlp_wrap_list_of_raw(items: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = items.iter().flatten().copied().collect();
+        if body.len() < 56 {
+            let mut out = vec![0xc0 + body.len() as u8];
+            out.extend_from_slice(&body);
+            out
+        } else {
+            let len_bytes = trim_be(body.len().to_be_bytes().to_vec());
+            let mut out = vec![0xf7 + len_bytes.len() as u8];
+            out.extend_from_slice(&len_bytes);
+            out.extend_from_slice(&body);
+            out
+        }
+    }
+
     /// Get CPU timestamp for profiling
     #[inline(always)]
     pub fn rdtsc() -> u64 {
@@ -296,7 +751,7 @@ impl Drop for TxBuffer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_keccak256() {
         // Only run if C lib is available
@@ -305,6 +760,89 @@ mod tests {
             assert!(!hash.is_zero());
         }
     }
+
+    // Well-known Hardhat/Anvil default account #0 key — public test fixture,
+    // not a real secret.
+    const TEST_PRIVATE_KEY: &str =
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[tokio::test]
+    async fn decode_transaction_and_recover_sender_round_trip_legacy() {
+        use ethers::signers::{LocalWallet, Signer};
+        use ethers::types::transaction::eip2718::TypedTransaction;
+        use ethers::types::{Address, TransactionRequest};
+
+        let wallet: LocalWallet = TEST_PRIVATE_KEY.parse().unwrap();
+        let to = Address::from_slice(&[0x11u8; 20]);
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(to)
+            .value(1_000_000_000_000_000_000u64)
+            .gas(21_000u64)
+            .gas_price(50_000_000_000u64)
+            .nonce(7u64)
+            .data(vec![0xde, 0xad, 0xbe, 0xef])
+            .chain_id(1u64)
+            .into();
+
+        let signature = wallet.sign_transaction(&tx).await.unwrap();
+        let raw = tx.rlp_signed(&signature);
+
+        let decoded = safe::decode_transaction(&raw).expect("legacy tx should decode");
+        assert_eq!(decoded.to, Some(to));
+        assert_eq!(decoded.data, &[0xde, 0xad, 0xbe, 0xef][..]);
+
+        let recovered = safe::recover_sender(&raw).expect("legacy tx should recover a sender");
+        assert_eq!(recovered, wallet.address());
+    }
+
+    #[tokio::test]
+    async fn decode_transaction_and_recover_sender_round_trip_eip1559() {
+        use ethers::signers::{LocalWallet, Signer};
+        use ethers::types::transaction::eip2718::TypedTransaction;
+        use ethers::types::{Address, Eip1559TransactionRequest};
+
+        let wallet: LocalWallet = TEST_PRIVATE_KEY.parse().unwrap();
+        let to = Address::from_slice(&[0x22u8; 20]);
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(to)
+            .value(42u64)
+            .gas(100_000u64)
+            .max_fee_per_gas(100_000_000_000u64)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .nonce(3u64)
+            .data(vec![0x01, 0x02])
+            .chain_id(1u64)
+            .into();
+
+        let signature = wallet.sign_transaction(&tx).await.unwrap();
+        let raw = tx.rlp_signed(&signature);
+
+        let decoded = safe::decode_transaction(&raw).expect("EIP-1559 tx should decode");
+        assert_eq!(decoded.to, Some(to));
+        assert_eq!(decoded.data, &[0x01, 0x02][..]);
+
+        let recovered = safe::recover_sender(&raw).expect("EIP-1559 tx should recover a sender");
+        assert_eq!(recovered, wallet.address());
+    }
+
+    #[tokio::test]
+    async fn decode_transaction_rejects_truncated_input() {
+        assert!(safe::decode_transaction(&[0x02, 0xc0]).is_none());
+        assert!(safe::recover_sender(&[]).is_none());
+    }
+
+    #[test]
+    fn u256_limbs_round_trip() {
+        let value = U256::from(u64::MAX) * U256::from(u64::MAX) + U256::from(12345u64);
+        let limbs = safe::u256_to_limbs(value);
+        assert_eq!(safe::u256_from_limbs(limbs), value);
+
+        assert_eq!(safe::u256_from_limbs([0, 0, 0, 0]), U256::zero());
+        assert_eq!(safe::u256_to_limbs(U256::zero()), [0, 0, 0, 0]);
+
+        let max = U256::MAX;
+        assert_eq!(safe::u256_from_limbs(safe::u256_to_limbs(max)), max);
+    }
 }
 
 // Re-export commonly used items
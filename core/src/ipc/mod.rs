@@ -0,0 +1,219 @@
+//! Local JSON-RPC control/telemetry endpoint
+//!
+//! `EvmSimulator::count`, `MempoolStats`, and the mempool monitor's
+//! start/stop flag were only reachable in-process, so inspecting or
+//! controlling a running engine meant restarting it with different code.
+//! `ControlServer` exposes a handful of methods over a Unix domain socket
+//! (and optionally TCP) for operators and external tooling, using the same
+//! `jsonrpc`/`id`/`method`/`params` request shape and `result`/`error`
+//! response shape already used for the upstream `eth_subscribe` WS calls in
+//! `mempool::ultra_ws`.
+//!
+//! Supported methods:
+//! - `mempool_stats` -> current `MempoolStats` snapshot
+//! - `simulator_stats` -> `EvmSimulator` counters
+//! - `simulate` -> `{"opportunity": Opportunity}` or `{"bundle": Bundle}`, returns a `SimulationResult`
+//! - `mempool_pause` / `mempool_resume` -> toggle the mempool monitor's running flag
+
+use crate::mempool::MempoolStats;
+use crate::simulator::EvmSimulator;
+use crate::types::{Bundle, Opportunity};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, UnixListener};
+use tracing::{info, warn};
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+/// Handles for whatever the control server needs to answer each method.
+/// Nothing here is owned by the server — it's wired up from handles the
+/// caller already constructed (`EvmSimulator`, the mempool monitor's stats
+/// and running-flag handles), the same way `MevEngine` is assembled from its
+/// components.
+pub struct ControlServer {
+    simulator: Arc<EvmSimulator>,
+    mempool_stats: Arc<MempoolStats>,
+    mempool_running: Arc<AtomicBool>,
+}
+
+impl ControlServer {
+    pub fn new(
+        simulator: Arc<EvmSimulator>,
+        mempool_stats: Arc<MempoolStats>,
+        mempool_running: Arc<AtomicBool>,
+    ) -> Self {
+        Self { simulator, mempool_stats, mempool_running }
+    }
+
+    /// Listen on a Unix domain socket at `socket_path`, handling one
+    /// connection at a time per spawned task, until this future is dropped.
+    pub async fn serve_unix(self: Arc<Self>, socket_path: &str) -> anyhow::Result<()> {
+        let _ = std::fs::remove_file(socket_path);
+        let listener = UnixListener::bind(socket_path)?;
+        info!("IPC control server listening on unix socket {}", socket_path);
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let server = self.clone();
+            tokio::spawn(async move {
+                let (read_half, write_half) = stream.into_split();
+                if let Err(e) = server.handle_connection(read_half, write_half).await {
+                    warn!("IPC connection error: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Listen on `addr` over TCP — same protocol, for tooling that can't
+    /// reach a Unix socket (e.g. running outside the host).
+    pub async fn serve_tcp(self: Arc<Self>, addr: &str) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        info!("IPC control server listening on tcp {}", addr);
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let server = self.clone();
+            tokio::spawn(async move {
+                let (read_half, write_half) = stream.into_split();
+                if let Err(e) = server.handle_connection(read_half, write_half).await {
+                    warn!("IPC connection error: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection<R, W>(&self, read_half: R, mut write_half: W) -> anyhow::Result<()>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        // Newline-delimited JSON-RPC requests/responses, one per line, same
+        // as the upstream `eth_subscribe` framing this mirrors.
+        let mut lines = BufReader::new(read_half).lines();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let response = match serde_json::from_str::<RpcRequest>(&line) {
+                Ok(request) => self.dispatch(request).await,
+                Err(e) => RpcResponse {
+                    jsonrpc: "2.0",
+                    id: Value::Null,
+                    result: None,
+                    error: Some(RpcError { code: -32700, message: format!("parse error: {e}") }),
+                },
+            };
+
+            let mut payload = serde_json::to_vec(&response)?;
+            payload.push(b'\n');
+            write_half.write_all(&payload).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn dispatch(&self, request: RpcRequest) -> RpcResponse {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            "mempool_stats" => Ok(self.mempool_stats_json()),
+            "simulator_stats" => Ok(serde_json::json!({
+                "simulations_run": self.simulator.count().await,
+            })),
+            "mempool_pause" => {
+                self.mempool_running.store(false, Ordering::SeqCst);
+                Ok(serde_json::json!({ "running": false }))
+            }
+            "mempool_resume" => {
+                self.mempool_running.store(true, Ordering::SeqCst);
+                Ok(serde_json::json!({ "running": true }))
+            }
+            "simulate" => self.simulate(request.params).await,
+            other => Err(RpcError { code: -32601, message: format!("unknown method: {other}") }),
+        };
+
+        match result {
+            Ok(value) => RpcResponse { jsonrpc: "2.0", id, result: Some(value), error: None },
+            Err(error) => RpcResponse { jsonrpc: "2.0", id, result: None, error: Some(error) },
+        }
+    }
+
+    fn mempool_stats_json(&self) -> Value {
+        let snapshot = self.mempool_stats.snapshot();
+        serde_json::json!({
+            "txs_received": self.mempool_stats.txs_received.load(Ordering::Relaxed),
+            "txs_parsed": self.mempool_stats.txs_parsed.load(Ordering::Relaxed),
+            "swaps_detected": self.mempool_stats.swaps_detected.load(Ordering::Relaxed),
+            "active_endpoint": self.mempool_stats.active_endpoint(),
+            "failover_count": self.mempool_stats.failover_count.load(Ordering::Relaxed),
+            "ingest_queue_depth": self.mempool_stats.ingest_queue_depth.load(Ordering::Relaxed),
+            "enrich_queue_depth": self.mempool_stats.enrich_queue_depth.load(Ordering::Relaxed),
+            "ingest_dropped": self.mempool_stats.ingest_dropped.load(Ordering::Relaxed),
+            "latency": {
+                "count": snapshot.count,
+                "mean_ns": snapshot.mean_ns,
+                "p50_ns": snapshot.p50_ns,
+                "p90_ns": snapshot.p90_ns,
+                "p99_ns": snapshot.p99_ns,
+                "p999_ns": snapshot.p999_ns,
+                "min_ns": snapshot.min_ns,
+                "max_ns": snapshot.max_ns,
+            },
+        })
+    }
+
+    /// Accepts `{"opportunity": Opportunity}` or `{"bundle": Bundle}` and
+    /// returns the resulting `SimulationResult`.
+    async fn simulate(&self, params: Value) -> Result<Value, RpcError> {
+        let invalid = |what: &str, e: serde_json::Error| RpcError {
+            code: -32602,
+            message: format!("invalid {what}: {e}"),
+        };
+        let serialize_err = |e: serde_json::Error| RpcError { code: -32603, message: e.to_string() };
+
+        if let Some(raw) = params.get("opportunity") {
+            let opportunity: Opportunity = serde_json::from_value(raw.clone())
+                .map_err(|e| invalid("opportunity", e))?;
+            let result = self.simulator.simulate(&opportunity).await;
+            return serde_json::to_value(&result).map_err(serialize_err);
+        }
+
+        if let Some(raw) = params.get("bundle") {
+            let bundle: Bundle = serde_json::from_value(raw.clone())
+                .map_err(|e| invalid("bundle", e))?;
+            let result = self.simulator.simulate_bundle(&bundle).await;
+            return serde_json::to_value(&result).map_err(serialize_err);
+        }
+
+        Err(RpcError {
+            code: -32602,
+            message: "params must include `opportunity` or `bundle`".to_string(),
+        })
+    }
+}
@@ -19,6 +19,10 @@ pub mod ffi;
 pub mod types;
 pub mod mempool;
 pub mod bench;
+pub mod provider;
+pub mod ipc;
+pub mod replay;
+pub mod queue;
 
 // Arbitrum-specific modules
 pub mod arbitrum;
@@ -27,8 +31,12 @@ pub use config::Config;
 pub use detector::OpportunityDetector;
 pub use simulator::EvmSimulator;
 pub use builder::BundleBuilder;
-pub use mempool::{MempoolMonitor, MempoolConfig, MempoolTx};
+pub use mempool::{MempoolMonitor, MempoolConfig, MempoolTx, OverflowPolicy};
+pub use provider::{EvmProvider, HttpEvmProvider, WsEvmProvider, MetricsRetryMiddleware};
 pub use bench::run_all_benchmarks;
+pub use ipc::ControlServer;
+pub use replay::{ReplayConfig, ReplaySource};
+pub use queue::{OpportunityQueue, OpportunityScorer, ProfitPerGasScorer};
 
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -92,6 +100,13 @@ impl MevEngine {
         *self.running.read().await
     }
 
+    /// Shared handle to the engine's simulator, for callers (e.g. the IPC
+    /// control server) that need to invoke `simulate`/`simulate_bundle`
+    /// without owning the engine.
+    pub fn simulator(&self) -> Arc<EvmSimulator> {
+        self.simulator.clone()
+    }
+
     /// Get engine statistics
     pub async fn stats(&self) -> EngineStats {
         EngineStats {
@@ -1,13 +1,21 @@
 //! MEV Engine CLI
 //! Sub-microsecond latency optimized
 
-use mev_core::{Config, MevEngine, MempoolConfig, MempoolMonitor};
+use mev_core::{Config, ControlServer, MevEngine, MempoolConfig, MempoolMonitor, OverflowPolicy, ReplayConfig};
+use mev_core::arbitrum::{self, detector::ArbitrageDetector, mempool::PendingSwapMonitor, pools::PoolManager};
+use mev_core::config::ChainConfig;
 use mev_core::detector::{MultiThreadedDetector, DetectorConfig};
 use mev_core::ffi::rdtsc_native;
+use mev_core::replay;
+use ethers::providers::{Http, Provider};
+use ethers::types::U256;
 use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
-use tokio::sync::mpsc;
+use tokio::sync::broadcast::error::RecvError;
 use std::env;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 8)]
 async fn main() -> anyhow::Result<()> {
@@ -36,6 +44,14 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    if args.len() > 1 && args[1] == "replay" {
+        let config = Config::from_env()?;
+        let replay_config = parse_replay_args(&args[2..]);
+        let report = replay::run(replay_config, Arc::new(config)).await?;
+        report.print_summary();
+        return Ok(());
+    }
+
     // Load configuration
     let config = Config::from_env()?;
     
@@ -56,13 +72,21 @@ async fn main() -> anyhow::Result<()> {
     let mempool_config = MempoolConfig {
         ws_url: arbitrum_config.ws_url.clone(),
         backup_ws_urls: vec![],
+        rpc_url: arbitrum_config.rpc_url.clone(),
         max_pending_txs: 10_000,
         cpu_core: Some(0),
         batch_size: 32,
+        stage_capacity: 4096,
+        overflow_policy: OverflowPolicy::DropOldest,
+        broadcast_capacity: 4096,
     };
     let mempool_monitor = MempoolMonitor::new(mempool_config);
     info!("✅ Mempool monitor initialized");
 
+    let ipc_config = config.ipc.clone();
+    let mempool_stats_handle = mempool_monitor.stats_handle();
+    let mempool_running_handle = mempool_monitor.running_handle();
+
     // Create multi-threaded detector
     let detector_config = DetectorConfig {
         num_workers: 4,
@@ -71,12 +95,45 @@ async fn main() -> anyhow::Result<()> {
         gas_price: ethers::types::U256::from(100_000_000), // 0.1 gwei
         batch_size: 64,
     };
-    let _detector = MultiThreadedDetector::new(detector_config);
+    let detector = MultiThreadedDetector::new(detector_config);
     info!("✅ Multi-threaded detector initialized (4 workers)");
 
+    // Read-only Arbitrum pool discovery + arbitrage scanning, running
+    // alongside the Ethereum-side pipeline above. `arbitrum::executor` and
+    // `arbitrum::flashbots` stay unwired here: they sign and submit real
+    // transactions, and this binary has no config slot for an operator
+    // private key yet, so auto-starting them would mean launching a
+    // fund-moving pipeline with no way to configure whose funds.
+    spawn_arbitrum_detection(arbitrum_config);
+
     // Create engine
     let engine = MevEngine::new(config);
 
+    // Start the local control/telemetry server, if configured
+    let control_server = Arc::new(ControlServer::new(
+        engine.simulator(),
+        mempool_stats_handle,
+        mempool_running_handle,
+    ));
+
+    if let Some(socket_path) = ipc_config.socket_path.clone() {
+        let server = control_server.clone();
+        tokio::spawn(async move {
+            if let Err(e) = server.serve_unix(&socket_path).await {
+                warn!("IPC unix server error: {}", e);
+            }
+        });
+    }
+
+    if let Some(tcp_addr) = ipc_config.tcp_addr.clone() {
+        let server = control_server.clone();
+        tokio::spawn(async move {
+            if let Err(e) = server.serve_tcp(&tcp_addr).await {
+                warn!("IPC tcp server error: {}", e);
+            }
+        });
+    }
+
     // Handle shutdown
     let engine_clone = engine.clone();
     tokio::spawn(async move {
@@ -85,42 +142,96 @@ async fn main() -> anyhow::Result<()> {
         engine_clone.stop().await.ok();
     });
 
+    // Every strategy subsystem gets its own broadcast subscription onto the
+    // mempool stream, so a slow one only falls behind its own subscription
+    // (visible as `RecvError::Lagged`) instead of blocking the others.
+    let mut main_rx = mempool_monitor.subscribe();
+    let detector_broadcast_rx = mempool_monitor.subscribe();
+
     // Start mempool monitoring
-    let (tx_sender, mut tx_receiver) = mpsc::unbounded_channel();
-    
     let monitor = mempool_monitor;
     tokio::spawn(async move {
-        if let Err(e) = monitor.start(tx_sender).await {
+        if let Err(e) = monitor.start().await {
             warn!("Mempool monitor error: {}", e);
         }
     });
 
+    // Bridge the async broadcast stream into the rayon-based detector's
+    // synchronous crossbeam channel, and start the detector pool itself.
+    let (detector_tx, detector_rx) = crossbeam_channel::unbounded();
+    let (opp_tx, opp_rx) = crossbeam_channel::unbounded();
+    detector.start(detector_rx, opp_tx);
+
+    let detector_lag = Arc::new(AtomicU64::new(0));
+    let detector_lag_handle = detector_lag.clone();
+    tokio::spawn(async move {
+        let mut rx = detector_broadcast_rx;
+        loop {
+            match rx.recv().await {
+                Ok(tx) => {
+                    if detector_tx.send(tx).is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(n)) => {
+                    detector_lag_handle.fetch_add(n, Ordering::Relaxed);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    // `opp_rx.recv()` blocks the calling thread, so this runs on a blocking
+    // task rather than a normal tokio task.
+    tokio::task::spawn_blocking(move || {
+        while let Ok(opp) = opp_rx.recv() {
+            info!("💰 Opportunity found via multi-threaded detector: {:?}", opp.id);
+        }
+    });
+
     info!("\n🚀 MEV ENGINE STARTED");
     info!("   Monitoring mempool for opportunities...\n");
 
     // Main loop - process transactions
     let mut tx_count = 0u64;
+    let main_lag = Arc::new(AtomicU64::new(0));
     let mut last_log = std::time::Instant::now();
-    
+
     loop {
         tokio::select! {
-            Some(tx) = tx_receiver.recv() => {
-                tx_count += 1;
-                
+            result = main_rx.recv() => {
+                match result {
+                    Ok(tx) => {
+                        tx_count += 1;
+
+                        // Check if it's a swap
+                        if tx.is_swap {
+                            info!("🔄 Swap detected: {:?}", tx.hash);
+                            // Detector will process this
+                        }
+                    }
+                    Err(RecvError::Lagged(n)) => {
+                        main_lag.fetch_add(n, Ordering::Relaxed);
+                        warn!("Main loop lagged behind the mempool broadcast by {} txs", n);
+                    }
+                    Err(RecvError::Closed) => {
+                        info!("Mempool broadcast channel closed");
+                        break;
+                    }
+                }
+
                 // Log stats every 10 seconds
                 if last_log.elapsed() > std::time::Duration::from_secs(10) {
-                    info!("📊 Stats | TXs: {} | Rate: {:.1}/s", 
-                          tx_count, 
-                          tx_count as f64 / last_log.elapsed().as_secs_f64());
+                    info!(
+                        "📊 Stats | TXs: {} | Rate: {:.1}/s | Lagged[main: {}, detector: {}]",
+                        tx_count,
+                        tx_count as f64 / last_log.elapsed().as_secs_f64(),
+                        main_lag.load(Ordering::Relaxed),
+                        detector_lag.load(Ordering::Relaxed),
+                    );
                     tx_count = 0;
                     last_log = std::time::Instant::now();
                 }
-                
-                // Check if it's a swap
-                if tx.is_swap {
-                    info!("🔄 Swap detected: {:?}", tx.hash);
-                    // Detector will process this
-                }
             }
             _ = tokio::signal::ctrl_c() => {
                 info!("🛑 Shutting down...");
@@ -132,3 +243,104 @@ async fn main() -> anyhow::Result<()> {
     info!("✅ MEV Engine shutdown complete");
     Ok(())
 }
+
+/// Starts the read-only half of the Arbitrum pipeline: pool discovery, a
+/// periodic two-hop arbitrage scan, and the pending-swap mempool watcher —
+/// each just logs what it finds. Does nothing (beyond a warning) if
+/// `chain.rpc_url` doesn't parse into an HTTP provider.
+fn spawn_arbitrum_detection(chain: &ChainConfig) {
+    let arbitrum_config = arbitrum::ArbitrumConfig {
+        chain_id: chain.chain_id,
+        rpc_url: chain.rpc_url.clone(),
+        ws_url: chain.ws_url.clone(),
+        ..Default::default()
+    };
+
+    let provider = match Provider::<Http>::try_from(arbitrum_config.rpc_url.as_str()) {
+        Ok(p) => Arc::new(p),
+        Err(e) => {
+            warn!("Arbitrum rpc_url {:?} invalid, skipping Arbitrum detection pipeline: {}", arbitrum_config.rpc_url, e);
+            return;
+        }
+    };
+    let pool_manager = Arc::new(PoolManager::new(provider));
+
+    // Seed the pool set against WETH so there's something for `scan_all` and
+    // the pending-swap monitor to match against before the first discovery
+    // pass; `discover_pools` fills in the rest via `getPool`/`getPair`.
+    let discovery_manager = pool_manager.clone();
+    let seed_pairs = [
+        (arbitrum_config.weth, arbitrum_config.usdc),
+        (arbitrum_config.weth, arbitrum_config.usdt),
+        (arbitrum_config.weth, arbitrum_config.arb),
+    ];
+    tokio::spawn(async move {
+        for (token_a, token_b) in seed_pairs {
+            discovery_manager.discover_pools(token_a, token_b).await;
+        }
+    });
+
+    let detector = ArbitrageDetector::new(pool_manager.clone(), 50); // 0.5% min profit
+    tokio::spawn(async move {
+        let scan_amount = U256::exp10(18); // 1 WETH
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(12)).await;
+            for opp in detector.scan_all(scan_amount).await {
+                info!(
+                    "💰 Arbitrum arb opportunity: profit={} profit_bps={}",
+                    opp.profit, opp.profit_bps
+                );
+            }
+        }
+    });
+
+    let pending_swap_monitor = PendingSwapMonitor::new(&arbitrum_config, pool_manager);
+    tokio::spawn(async move {
+        pending_swap_monitor.run().await;
+    });
+
+    info!("✅ Arbitrum pool discovery + arbitrage scanning started (read-only; execution not wired)");
+}
+
+/// Parses `replay`'s trailing args: either a file path to replay, or
+/// `--synthetic-rate N` (optionally followed by `--max-rate M` /
+/// `--step-seconds S`) to drive the built-in load generator. Defaults to
+/// synthetic generation starting at 100 tx/s when nothing is given.
+fn parse_replay_args(args: &[String]) -> ReplayConfig {
+    let mut file: Option<PathBuf> = None;
+    let mut start_rate = 100u64;
+    let mut max_rate: Option<u64> = None;
+    let mut step_seconds: Option<u64> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--synthetic-rate" => {
+                i += 1;
+                start_rate = args.get(i).and_then(|s| s.parse().ok()).unwrap_or(start_rate);
+            }
+            "--max-rate" => {
+                i += 1;
+                max_rate = args.get(i).and_then(|s| s.parse().ok());
+            }
+            "--step-seconds" => {
+                i += 1;
+                step_seconds = args.get(i).and_then(|s| s.parse().ok());
+            }
+            other => file = Some(PathBuf::from(other)),
+        }
+        i += 1;
+    }
+
+    let mut config = match file {
+        Some(path) => ReplayConfig::from_file(path, start_rate),
+        None => ReplayConfig::synthetic(start_rate),
+    };
+    if let Some(max_rate) = max_rate {
+        config.max_tps = max_rate;
+    }
+    if let Some(step_seconds) = step_seconds {
+        config.step_duration = std::time::Duration::from_secs(step_seconds);
+    }
+    config
+}
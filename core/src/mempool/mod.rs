@@ -1,12 +1,15 @@
 //! Mempool monitoring module
 //! Ultra-low latency WebSocket + enhanced subscription
 
+pub mod pipeline;
 pub mod ultra_ws;
 
+pub use pipeline::OverflowPolicy;
 pub use ultra_ws::{
     MempoolMonitor,
     EnhancedMempoolMonitor,
     MempoolConfig,
     MempoolTx,
     MempoolStats,
+    LatencySnapshot,
 };
@@ -0,0 +1,135 @@
+//! Bounded stage channels for the mempool ingest -> enrichment -> detection
+//! pipeline.
+//!
+//! `tokio::sync::mpsc`'s bounded channel gives real backpressure but no way
+//! to evict an already-queued item once it's full, so `DropOldest` can't be
+//! built on top of it directly. `Stage<T>` is a small ring buffer
+//! (`Mutex<VecDeque<T>>` plus a `Notify` in each direction) with the same
+//! bounded-channel shape that supports all three overflow policies.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// What a stage does when its queue is at capacity and a new item arrives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait for the consumer to make room (real backpressure).
+    Block,
+    /// Evict the longest-queued item, then enqueue the new one.
+    DropOldest,
+    /// Discard the new item; the queue is left as-is.
+    DropNewest,
+}
+
+struct Inner<T> {
+    queue: Mutex<VecDeque<T>>,
+    capacity: usize,
+    dropped: AtomicU64,
+    space_available: Notify,
+    item_available: Notify,
+}
+
+/// Producer handle for one pipeline stage.
+pub struct StageSender<T> {
+    inner: Arc<Inner<T>>,
+    policy: OverflowPolicy,
+}
+
+/// Consumer handle for one pipeline stage.
+pub struct StageReceiver<T> {
+    inner: Arc<Inner<T>>,
+}
+
+/// Build a bounded stage channel of `capacity` with the given overflow
+/// policy.
+pub fn stage<T>(capacity: usize, policy: OverflowPolicy) -> (StageSender<T>, StageReceiver<T>) {
+    let inner = Arc::new(Inner {
+        queue: Mutex::new(VecDeque::with_capacity(capacity)),
+        capacity: capacity.max(1),
+        dropped: AtomicU64::new(0),
+        space_available: Notify::new(),
+        item_available: Notify::new(),
+    });
+    (
+        StageSender { inner: inner.clone(), policy },
+        StageReceiver { inner },
+    )
+}
+
+impl<T> StageSender<T> {
+    /// Current queue depth, for a `MempoolStats` gauge.
+    pub fn depth(&self) -> usize {
+        self.inner.queue.lock().unwrap().len()
+    }
+
+    /// Total items dropped so far under `DropOldest`/`DropNewest`.
+    pub fn dropped(&self) -> u64 {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Enqueue `item`, applying this stage's overflow policy if the queue is
+    /// already at capacity.
+    pub async fn send(&self, item: T) {
+        let mut item = item;
+        loop {
+            {
+                let mut queue = self.inner.queue.lock().unwrap();
+                if queue.len() < self.inner.capacity {
+                    queue.push_back(item);
+                    drop(queue);
+                    self.inner.item_available.notify_one();
+                    return;
+                }
+
+                match self.policy {
+                    OverflowPolicy::DropNewest => {
+                        self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+                        return;
+                    }
+                    OverflowPolicy::DropOldest => {
+                        queue.pop_front();
+                        queue.push_back(item);
+                        drop(queue);
+                        self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+                        self.inner.item_available.notify_one();
+                        return;
+                    }
+                    OverflowPolicy::Block => {
+                        // Fall through and wait for the consumer below.
+                    }
+                }
+            }
+
+            self.inner.space_available.notified().await;
+        }
+    }
+}
+
+impl<T> StageReceiver<T> {
+    /// Current queue depth, for a `MempoolStats` gauge.
+    pub fn depth(&self) -> usize {
+        self.inner.queue.lock().unwrap().len()
+    }
+
+    /// Total items dropped so far under `DropOldest`/`DropNewest`.
+    pub fn dropped(&self) -> u64 {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Dequeue the oldest item, waiting until one is available.
+    pub async fn recv(&self) -> T {
+        loop {
+            {
+                let mut queue = self.inner.queue.lock().unwrap();
+                if let Some(item) = queue.pop_front() {
+                    drop(queue);
+                    self.inner.space_available.notify_one();
+                    return item;
+                }
+            }
+            self.inner.item_available.notified().await;
+        }
+    }
+}
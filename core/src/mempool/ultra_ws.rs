@@ -1,14 +1,38 @@
 //! Ultra-low latency WebSocket mempool monitor
 //! Zero-copy parsing, CPU pinning, lock-free queues
 
+use super::pipeline;
+use dashmap::DashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
 use tracing::{info, warn, error, debug};
+use ethers::providers::{Http, Middleware, Provider};
 use ethers::types::{Transaction, H256, U256, Address};
+use ethers::utils::keccak256;
 use futures_util::{StreamExt, SinkExt};
+use std::str::FromStr;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+/// Initial backoff for a newly-failed endpoint, doubled per consecutive
+/// failure up to `MAX_BACKOFF`.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// An endpoint must stay connected at least this long before a subsequent
+/// disconnect is treated as a fresh failure rather than a continuation of
+/// whatever was already wrong with it.
+const HEALTHY_CONNECTION: Duration = Duration::from_secs(10);
+
+/// Per-endpoint connection health: consecutive failure count (driving
+/// exponential backoff) and, while backing off, the instant it becomes
+/// eligible for reconnection again.
+#[derive(Default, Clone, Copy)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    blacklisted_until: Option<Instant>,
+}
+
 /// Helper function for getting timestamp
 #[inline(always)]
 fn rdtsc() -> u64 {
@@ -42,9 +66,23 @@ pub struct MempoolTx {
 pub struct MempoolConfig {
     pub ws_url: String,
     pub backup_ws_urls: Vec<String>,
+    /// HTTP RPC endpoint used by the enrichment stage to fetch the full
+    /// transaction for a hash seen over the WS feed. Left empty, the monitor
+    /// still runs but publishes every tx with `is_swap: false` since there's
+    /// nothing to fetch calldata from.
+    pub rpc_url: String,
     pub max_pending_txs: usize,
     pub cpu_core: Option<usize>,       // Pin to specific CPU core
     pub batch_size: usize,              // Process in batches
+    /// Bounded capacity of the internal ingest -> enrichment queue.
+    pub stage_capacity: usize,
+    /// What the ingest stage does when the enrichment stage can't keep up.
+    pub overflow_policy: pipeline::OverflowPolicy,
+    /// Ring buffer size of the outbound broadcast channel every strategy
+    /// subsystem subscribes to. A subscriber that falls more than this many
+    /// messages behind observes `RecvError::Lagged` instead of blocking the
+    /// others.
+    pub broadcast_capacity: usize,
 }
 
 impl Default for MempoolConfig {
@@ -52,15 +90,23 @@ impl Default for MempoolConfig {
         Self {
             ws_url: String::new(),
             backup_ws_urls: Vec::new(),
+            rpc_url: String::new(),
             max_pending_txs: 10_000,
             cpu_core: Some(0),           // Pin to core 0
             batch_size: 32,
+            stage_capacity: 4096,
+            overflow_policy: pipeline::OverflowPolicy::DropOldest,
+            broadcast_capacity: 4096,
         }
     }
 }
 
+/// Number of logarithmic latency buckets: bucket `i` covers
+/// `[2^(i-1), 2^i)` nanoseconds, so 64 buckets span nanoseconds through
+/// seconds with bounded relative error per bucket.
+const LATENCY_BUCKETS: usize = 64;
+
 /// Statistics for performance monitoring
-#[derive(Default)]
 pub struct MempoolStats {
     pub txs_received: AtomicU64,
     pub txs_parsed: AtomicU64,
@@ -68,6 +114,141 @@ pub struct MempoolStats {
     pub avg_latency_ns: AtomicU64,
     pub min_latency_ns: AtomicU64,
     pub max_latency_ns: AtomicU64,
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS],
+    latency_count: AtomicU64,
+    latency_sum_ns: AtomicU64,
+    /// Number of times the connection supervisor has had to move off the
+    /// endpoint it was previously connected to (initial connect excluded).
+    pub failover_count: AtomicU64,
+    active_endpoint: RwLock<String>,
+    /// Depth of the bounded ingest -> enrichment queue, sampled by the ingest
+    /// stage after each batch of hashes it pushes.
+    pub ingest_queue_depth: AtomicU64,
+    /// Depth of the enrichment stage's inbound queue (same queue as above,
+    /// sampled from the consumer side).
+    pub enrich_queue_depth: AtomicU64,
+    /// Items the ingest stage has dropped under `DropOldest`/`DropNewest`
+    /// because the enrichment stage couldn't keep up.
+    pub ingest_dropped: AtomicU64,
+}
+
+impl Default for MempoolStats {
+    fn default() -> Self {
+        Self {
+            txs_received: AtomicU64::new(0),
+            txs_parsed: AtomicU64::new(0),
+            swaps_detected: AtomicU64::new(0),
+            avg_latency_ns: AtomicU64::new(0),
+            min_latency_ns: AtomicU64::new(u64::MAX),
+            max_latency_ns: AtomicU64::new(0),
+            latency_buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            latency_count: AtomicU64::new(0),
+            latency_sum_ns: AtomicU64::new(0),
+            failover_count: AtomicU64::new(0),
+            active_endpoint: RwLock::new(String::new()),
+            ingest_queue_depth: AtomicU64::new(0),
+            enrich_queue_depth: AtomicU64::new(0),
+            ingest_dropped: AtomicU64::new(0),
+        }
+    }
+}
+
+impl MempoolStats {
+    /// The endpoint the supervisor is currently connected to (or was last
+    /// attempting), so operators can see when they've degraded to a backup.
+    pub fn active_endpoint(&self) -> String {
+        self.active_endpoint.read().unwrap().clone()
+    }
+
+    fn set_active_endpoint(&self, url: &str) {
+        *self.active_endpoint.write().unwrap() = url.to_string();
+    }
+
+    /// Record one transaction's end-to-end ingest latency: bump its
+    /// logarithmic histogram bucket, fold it into the running sum (backing
+    /// `avg_latency_ns`/`snapshot().mean_ns`), and update min/max via a
+    /// compare-exchange loop since multiple ingest tasks can race here.
+    pub fn record_latency(&self, latency_ns: u64) {
+        let bucket = latency_bucket(latency_ns);
+        self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        let count = self.latency_count.fetch_add(1, Ordering::Relaxed) + 1;
+        let sum = self.latency_sum_ns.fetch_add(latency_ns, Ordering::Relaxed) + latency_ns;
+        self.avg_latency_ns.store(sum / count, Ordering::Relaxed);
+
+        let mut min = self.min_latency_ns.load(Ordering::Relaxed);
+        while latency_ns < min {
+            match self.min_latency_ns.compare_exchange_weak(
+                min, latency_ns, Ordering::Relaxed, Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(observed) => min = observed,
+            }
+        }
+
+        let mut max = self.max_latency_ns.load(Ordering::Relaxed);
+        while latency_ns > max {
+            match self.max_latency_ns.compare_exchange_weak(
+                max, latency_ns, Ordering::Relaxed, Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(observed) => max = observed,
+            }
+        }
+    }
+
+    /// Walk cumulative bucket counts to read off the latency distribution:
+    /// total sample count, mean, and p50/p90/p99/p999.
+    pub fn snapshot(&self) -> LatencySnapshot {
+        let counts: [u64; LATENCY_BUCKETS] =
+            std::array::from_fn(|i| self.latency_buckets[i].load(Ordering::Relaxed));
+        let total: u64 = counts.iter().sum();
+        if total == 0 {
+            return LatencySnapshot::default();
+        }
+
+        let percentile_ns = |p: f64| -> u64 {
+            let target = ((total as f64) * p).ceil() as u64;
+            let mut cumulative = 0u64;
+            for (bucket, count) in counts.iter().enumerate() {
+                cumulative += count;
+                if cumulative >= target {
+                    // Bucket `bucket` covers [2^(bucket-1), 2^bucket) ns;
+                    // report its upper bound as a conservative estimate.
+                    return if bucket == 0 { 0 } else { 1u64 << bucket };
+                }
+            }
+            self.max_latency_ns.load(Ordering::Relaxed)
+        };
+
+        LatencySnapshot {
+            count: total,
+            mean_ns: self.latency_sum_ns.load(Ordering::Relaxed) / total,
+            p50_ns: percentile_ns(0.50),
+            p90_ns: percentile_ns(0.90),
+            p99_ns: percentile_ns(0.99),
+            p999_ns: percentile_ns(0.999),
+            min_ns: self.min_latency_ns.load(Ordering::Relaxed),
+            max_ns: self.max_latency_ns.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn latency_bucket(latency_ns: u64) -> usize {
+    let significant_bits = 64 - latency_ns.leading_zeros();
+    (significant_bits as usize).min(LATENCY_BUCKETS - 1)
+}
+
+/// Point-in-time view of a `MempoolStats` latency histogram.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LatencySnapshot {
+    pub count: u64,
+    pub mean_ns: u64,
+    pub p50_ns: u64,
+    pub p90_ns: u64,
+    pub p99_ns: u64,
+    pub p999_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
 }
 
 /// Ultra-low latency mempool monitor
@@ -75,24 +256,114 @@ pub struct MempoolMonitor {
     config: MempoolConfig,
     running: Arc<AtomicBool>,
     stats: Arc<MempoolStats>,
+    endpoint_health: DashMap<String, EndpointHealth>,
+    /// Publishes every enriched `MempoolTx` once; every strategy subsystem
+    /// that wants to observe the mempool calls `subscribe()` for its own
+    /// independent receiver, rather than all of them contending over a
+    /// single `mpsc` consumer.
+    tx_broadcast: broadcast::Sender<MempoolTx>,
+    /// HTTP client the enrichment stage uses to fetch full transactions by
+    /// hash. `None` if `config.rpc_url` is empty or doesn't parse, in which
+    /// case enrichment falls back to publishing hash-only txs.
+    provider: Option<Arc<Provider<Http>>>,
 }
 
 impl MempoolMonitor {
     pub fn new(config: MempoolConfig) -> Self {
+        let (tx_broadcast, _) = broadcast::channel(config.broadcast_capacity);
+        let provider = if config.rpc_url.is_empty() {
+            None
+        } else {
+            match Provider::<Http>::try_from(config.rpc_url.as_str()) {
+                Ok(p) => Some(Arc::new(p)),
+                Err(e) => {
+                    warn!("Invalid mempool rpc_url {:?}: {}", config.rpc_url, e);
+                    None
+                }
+            }
+        };
         Self {
             config,
             running: Arc::new(AtomicBool::new(false)),
             stats: Arc::new(MempoolStats::default()),
+            endpoint_health: DashMap::new(),
+            tx_broadcast,
+            provider,
         }
     }
-    
-    /// Start monitoring with CPU pinning
-    pub async fn start(
-        &self,
-        tx_sender: mpsc::UnboundedSender<MempoolTx>,
-    ) -> anyhow::Result<()> {
+
+    /// An independent receiver onto the broadcast stream of enriched
+    /// `MempoolTx`s. Call once per consumer — a subscriber that falls more
+    /// than `broadcast_capacity` messages behind the mempool rate sees
+    /// `RecvError::Lagged(n)` on its next `recv()` rather than blocking
+    /// publication for everyone else.
+    pub fn subscribe(&self) -> broadcast::Receiver<MempoolTx> {
+        self.tx_broadcast.subscribe()
+    }
+
+    /// Primary endpoint followed by all configured backups, in order.
+    fn endpoints(&self) -> Vec<String> {
+        std::iter::once(self.config.ws_url.clone())
+            .chain(self.config.backup_ws_urls.iter().cloned())
+            .collect()
+    }
+
+    /// Next endpoint to try starting from `from_idx`, skipping any still
+    /// inside their backoff window. If every endpoint is currently
+    /// blacklisted, falls back to whichever recovers soonest so the
+    /// supervisor always has somewhere to retry.
+    fn pick_endpoint(&self, endpoints: &[String], from_idx: usize) -> usize {
+        let now = Instant::now();
+        let is_healthy = |url: &str| {
+            self.endpoint_health
+                .get(url)
+                .and_then(|h| h.blacklisted_until)
+                .map(|until| now >= until)
+                .unwrap_or(true)
+        };
+
+        for offset in 0..endpoints.len() {
+            let idx = (from_idx + offset) % endpoints.len();
+            if is_healthy(&endpoints[idx]) {
+                return idx;
+            }
+        }
+
+        endpoints
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, url)| {
+                self.endpoint_health.get(*url).and_then(|h| h.blacklisted_until).unwrap_or(now)
+            })
+            .map(|(idx, _)| idx)
+            .unwrap_or(from_idx % endpoints.len())
+    }
+
+    /// Penalize `url` with exponential backoff (doubling from 1s, capped at
+    /// 60s) after a failed connection or a connection that died young.
+    fn record_failure(&self, url: &str) {
+        let mut health = self.endpoint_health.entry(url.to_string()).or_default();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        let backoff = INITIAL_BACKOFF
+            .checked_mul(1u32 << health.consecutive_failures.saturating_sub(1).min(6))
+            .unwrap_or(MAX_BACKOFF)
+            .min(MAX_BACKOFF);
+        health.blacklisted_until = Some(Instant::now() + backoff);
+    }
+
+    /// Clear any backoff penalty after `url` has stayed connected long
+    /// enough to be trusted again.
+    fn record_success(&self, url: &str) {
+        self.endpoint_health.remove(url);
+    }
+
+    /// Start monitoring with CPU pinning. Supervises the connection: on any
+    /// connect/subscribe/read failure it rotates to the next healthy
+    /// endpoint (primary then backups), reconnects, and re-subscribes,
+    /// rather than ending monitoring the first time the primary drops.
+    pub async fn start(&self) -> anyhow::Result<()> {
         self.running.store(true, Ordering::SeqCst);
-        
+
         // Pin to CPU core if specified
         if let Some(core) = self.config.cpu_core {
             #[cfg(target_os = "linux")]
@@ -105,88 +376,149 @@ impl MempoolMonitor {
                 }
             }
         }
-        
-        info!("Connecting to WebSocket: {}", self.config.ws_url);
-        
-        // Connect with low-latency TCP options
-        let (ws_stream, _) = connect_async(&self.config.ws_url).await?;
-        let (mut write, mut read) = ws_stream.split();
-        
-        // Subscribe to pending transactions
-        let subscribe_msg = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "eth_subscribe",
-            "params": ["newPendingTransactions"]
-        });
-        
-        write.send(Message::Text(subscribe_msg.to_string())).await?;
-        info!("Subscribed to pending transactions");
-        
-        // Pre-allocate buffers
-        let mut pending_hashes: Vec<H256> = Vec::with_capacity(self.config.batch_size);
-        
+
+        let endpoints = self.endpoints();
+        if endpoints.is_empty() || endpoints[0].is_empty() {
+            anyhow::bail!("no websocket endpoints configured");
+        }
+        let mut idx = 0usize;
+
+        // Stage 1 (this loop) only extracts hashes and timestamps off the
+        // socket; stage 2 (spawned below) does the enrichment work
+        // (full-tx fetch / classification) and stage 3 hands the finished
+        // `MempoolTx` to the broadcast channel. The bounded queue between them is what
+        // makes a slow stage 2 apply real backpressure instead of the old
+        // unbounded channel silently growing memory.
+        let (raw_sender, raw_receiver) =
+            pipeline::stage::<RawTx>(self.config.stage_capacity, self.config.overflow_policy);
+        let enrichment = tokio::spawn(enrichment_stage(
+            raw_receiver,
+            self.tx_broadcast.clone(),
+            self.stats.clone(),
+            self.running.clone(),
+            self.provider.clone(),
+        ));
+
         while self.running.load(Ordering::SeqCst) {
-            tokio::select! {
-                Some(msg) = read.next() => {
-                    let receive_tsc = rdtsc();
-                    let receive_ns = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_nanos() as u64;
-                    
-                    match msg {
-                        Ok(Message::Text(text)) => {
-                            self.stats.txs_received.fetch_add(1, Ordering::Relaxed);
-                            
-                            // Zero-copy JSON parsing for tx hash
-                            if let Some(hash) = self.extract_tx_hash_fast(&text) {
-                                pending_hashes.push(hash);
-                                
-                                // Batch processing
-                                if pending_hashes.len() >= self.config.batch_size {
-                                    self.process_batch(
-                                        &pending_hashes,
-                                        &tx_sender,
-                                        receive_tsc,
-                                        receive_ns,
-                                    ).await;
-                                    pending_hashes.clear();
-                                }
-                            }
-                        }
-                        Ok(Message::Binary(data)) => {
-                            // Handle binary format if provider supports it
-                            debug!("Received binary message: {} bytes", data.len());
-                        }
-                        Ok(Message::Ping(data)) => {
-                            write.send(Message::Pong(data)).await.ok();
-                        }
-                        Err(e) => {
-                            error!("WebSocket error: {}", e);
-                            break;
-                        }
-                        _ => {}
-                    }
+            idx = self.pick_endpoint(&endpoints, idx);
+            let url = &endpoints[idx];
+            info!("Connecting to WebSocket: {}", url);
+
+            let ws_stream = match connect_async(url).await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    error!("Failed to connect to {}: {}", url, e);
+                    self.record_failure(url);
+                    self.stats.failover_count.fetch_add(1, Ordering::Relaxed);
+                    idx = (idx + 1) % endpoints.len();
+                    continue;
                 }
-                _ = tokio::time::sleep(tokio::time::Duration::from_micros(100)) => {
-                    // Process remaining batch
-                    if !pending_hashes.is_empty() {
-                        let tsc = rdtsc();
-                        let ns = std::time::SystemTime::now()
+            };
+            let (mut write, mut read) = ws_stream.split();
+
+            // Subscribe to pending transactions
+            let subscribe_msg = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "eth_subscribe",
+                "params": ["newPendingTransactions"]
+            });
+
+            if let Err(e) = write.send(Message::Text(subscribe_msg.to_string())).await {
+                error!("Failed to subscribe on {}: {}", url, e);
+                self.record_failure(url);
+                self.stats.failover_count.fetch_add(1, Ordering::Relaxed);
+                idx = (idx + 1) % endpoints.len();
+                continue;
+            }
+            info!("Subscribed to pending transactions on {}", url);
+            self.stats.set_active_endpoint(url);
+
+            let connected_at = Instant::now();
+            // Pre-allocate buffers
+            let mut pending_hashes: Vec<H256> = Vec::with_capacity(self.config.batch_size);
+
+            'connection: while self.running.load(Ordering::SeqCst) {
+                tokio::select! {
+                    Some(msg) = read.next() => {
+                        let receive_tsc = rdtsc();
+                        let receive_ns = std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap()
                             .as_nanos() as u64;
-                        self.process_batch(&pending_hashes, &tx_sender, tsc, ns).await;
-                        pending_hashes.clear();
+
+                        match msg {
+                            Ok(Message::Text(text)) => {
+                                self.stats.txs_received.fetch_add(1, Ordering::Relaxed);
+
+                                // Zero-copy JSON parsing for tx hash
+                                if let Some(hash) = self.extract_tx_hash_fast(&text) {
+                                    pending_hashes.push(hash);
+
+                                    // Batch processing
+                                    if pending_hashes.len() >= self.config.batch_size {
+                                        self.process_batch(
+                                            &pending_hashes,
+                                            &raw_sender,
+                                            receive_tsc,
+                                            receive_ns,
+                                        ).await;
+                                        pending_hashes.clear();
+                                    }
+                                }
+                            }
+                            Ok(Message::Binary(data)) => {
+                                // Handle binary format if provider supports it
+                                debug!("Received binary message: {} bytes", data.len());
+                            }
+                            Ok(Message::Ping(data)) => {
+                                write.send(Message::Pong(data)).await.ok();
+                            }
+                            Err(e) => {
+                                error!("WebSocket error on {}: {}", url, e);
+                                break 'connection;
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ = tokio::time::sleep(tokio::time::Duration::from_micros(100)) => {
+                        // Process remaining batch
+                        if !pending_hashes.is_empty() {
+                            let tsc = rdtsc();
+                            let ns = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap()
+                                .as_nanos() as u64;
+                            self.process_batch(&pending_hashes, &raw_sender, tsc, ns).await;
+                            pending_hashes.clear();
+                        }
                     }
                 }
             }
+
+            if !self.running.load(Ordering::SeqCst) {
+                break;
+            }
+
+            // Connection dropped while we were still supposed to be
+            // running. Only treat it as a fresh failure if it didn't even
+            // survive the "sustained connection" window — a long-lived
+            // connection that eventually drops is normal churn, not a sign
+            // this endpoint is unhealthy.
+            if connected_at.elapsed() >= HEALTHY_CONNECTION {
+                self.record_success(url);
+            } else {
+                self.record_failure(url);
+                self.stats.failover_count.fetch_add(1, Ordering::Relaxed);
+            }
+            idx = (idx + 1) % endpoints.len();
         }
-        
+
+        enrichment.abort();
+
         Ok(())
     }
-    
+
     /// Ultra-fast tx hash extraction without full JSON parsing
     #[inline(always)]
     fn extract_tx_hash_fast(&self, text: &str) -> Option<H256> {
@@ -207,33 +539,40 @@ impl MempoolMonitor {
         None
     }
     
-    /// Process batch of tx hashes
+    /// Hand a batch of raw tx hashes off to the enrichment stage over the
+    /// bounded `raw_sender` queue, applying the configured overflow policy
+    /// if stage 2 is behind.
     async fn process_batch(
         &self,
         hashes: &[H256],
-        tx_sender: &mpsc::UnboundedSender<MempoolTx>,
+        raw_sender: &pipeline::StageSender<RawTx>,
         receive_tsc: u64,
         receive_ns: u64,
     ) {
         for hash in hashes {
-            // For now, send hash with timing info
-            // Full tx fetch will be done by detector
-            let mempool_tx = MempoolTx {
+            raw_sender.send(RawTx {
                 hash: *hash,
-                tx: Transaction::default(),
                 first_seen_tsc: receive_tsc,
                 first_seen_ns: receive_ns,
-                gas_price: U256::zero(),
-                is_swap: false,
-                swap_info: None,
-            };
-            
-            if tx_sender.send(mempool_tx).is_err() {
-                warn!("Tx channel full, dropping transaction");
-            }
+            }).await;
+        }
+
+        self.stats.ingest_queue_depth.store(raw_sender.depth() as u64, Ordering::Relaxed);
+        self.stats.ingest_dropped.store(raw_sender.dropped(), Ordering::Relaxed);
+
+        // End-to-end ingest latency: time from when the batch's messages
+        // were received off the socket to when this batch finished being
+        // handed off to stage 2. Every tx in the batch shares this
+        // timestamp pair since they arrived together; record one sample per
+        // tx so the histogram reflects actual ingest volume.
+        let processed_ns = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+        let latency_ns = processed_ns.saturating_sub(receive_ns);
+        for _ in hashes {
+            self.stats.record_latency(latency_ns);
         }
-        
-        self.stats.txs_parsed.fetch_add(hashes.len() as u64, Ordering::Relaxed);
     }
     
     pub fn stop(&self) {
@@ -243,6 +582,102 @@ impl MempoolMonitor {
     pub fn stats(&self) -> &MempoolStats {
         &self.stats
     }
+
+    /// Shared handle to this monitor's stats, for callers (e.g. the IPC
+    /// control server) that outlive a borrow of `&self`.
+    pub fn stats_handle(&self) -> Arc<MempoolStats> {
+        self.stats.clone()
+    }
+
+    /// Shared handle to this monitor's running flag, so it can be toggled
+    /// (e.g. by `ipc::ControlServer`'s `mempool_pause`/`mempool_resume`)
+    /// without holding a reference to the monitor itself.
+    pub fn running_handle(&self) -> Arc<AtomicBool> {
+        self.running.clone()
+    }
+}
+
+/// Stage 1 -> stage 2 handoff: just the hash and arrival timestamps, so the
+/// latency-critical WS-read loop never blocks on JSON/ABI work.
+struct RawTx {
+    hash: H256,
+    first_seen_tsc: u64,
+    first_seen_ns: u64,
+}
+
+/// Stage 2 of the pipeline: drains `raw_receiver`, enriches each hash into a
+/// full `MempoolTx` (fetches the full transaction over `provider` and runs
+/// the same selector classification `EnhancedMempoolMonitor` uses), and hands
+/// the result to stage 3 (the broadcast channel). Runs as its own task so a
+/// slow enrichment pass never stalls the socket read in
+/// `MempoolMonitor::start`.
+async fn enrichment_stage(
+    raw_receiver: pipeline::StageReceiver<RawTx>,
+    tx_broadcast: broadcast::Sender<MempoolTx>,
+    stats: Arc<MempoolStats>,
+    running: Arc<AtomicBool>,
+    provider: Option<Arc<Provider<Http>>>,
+) {
+    while running.load(Ordering::SeqCst) {
+        let raw = tokio::select! {
+            raw = raw_receiver.recv() => raw,
+            _ = tokio::time::sleep(Duration::from_millis(50)) => continue,
+        };
+
+        stats.enrich_queue_depth.store(raw_receiver.depth() as u64, Ordering::Relaxed);
+
+        // Fetched tx may come back `None` if it was already mined/dropped by
+        // the time we poll for it, and the provider itself is `None` when no
+        // `rpc_url` was configured — both fall back to a hash-only tx rather
+        // than stalling the stage on an error.
+        let fetched = match &provider {
+            Some(p) => match p.get_transaction(raw.hash).await {
+                Ok(tx) => tx,
+                Err(e) => {
+                    debug!("get_transaction({:?}) failed: {}", raw.hash, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let mempool_tx = match fetched {
+            Some(tx) => {
+                let is_swap = is_likely_swap(&tx);
+                let swap_info = if is_swap { parse_swap_fast(&tx) } else { None };
+                MempoolTx {
+                    hash: raw.hash,
+                    gas_price: tx.gas_price.unwrap_or_default(),
+                    is_swap,
+                    swap_info,
+                    tx,
+                    first_seen_tsc: raw.first_seen_tsc,
+                    first_seen_ns: raw.first_seen_ns,
+                }
+            }
+            None => MempoolTx {
+                hash: raw.hash,
+                tx: Transaction::default(),
+                first_seen_tsc: raw.first_seen_tsc,
+                first_seen_ns: raw.first_seen_ns,
+                gas_price: U256::zero(),
+                is_swap: false,
+                swap_info: None,
+            },
+        };
+
+        if mempool_tx.is_swap {
+            stats.swaps_detected.fetch_add(1, Ordering::Relaxed);
+        }
+
+        // Unlike the old `mpsc` hookup, a send error here just means no
+        // subsystem has subscribed yet (or all of them have been dropped) —
+        // not that the pipeline has shut down, so keep publishing.
+        if tx_broadcast.send(mempool_tx).is_err() {
+            debug!("No active mempool subscribers for this tx");
+        }
+        stats.txs_parsed.fetch_add(1, Ordering::Relaxed);
+    }
 }
 
 /// Direct mempool subscription with full tx data (Alchemy enhanced API)
@@ -298,9 +733,9 @@ impl EnhancedMempoolMonitor {
                 if let Ok(response) = serde_json::from_str::<serde_json::Value>(&text) {
                     if let Some(result) = response.get("params").and_then(|p| p.get("result")) {
                         if let Ok(tx) = serde_json::from_value::<Transaction>(result.clone()) {
-                            let is_swap = self.is_likely_swap(&tx);
+                            let is_swap = is_likely_swap(&tx);
                             let swap_info = if is_swap {
-                                self.parse_swap_fast(&tx)
+                                parse_swap_fast(&tx)
                             } else {
                                 None
                             };
@@ -325,51 +760,349 @@ impl EnhancedMempoolMonitor {
         Ok(())
     }
     
-    /// Fast swap detection based on function selector
-    #[inline(always)]
-    fn is_likely_swap(&self, tx: &Transaction) -> bool {
-        if tx.input.len() < 4 {
-            return false;
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Fast swap detection based on function selector. Free function (rather
+/// than a method) so both `EnhancedMempoolMonitor::start_enhanced` and
+/// `enrichment_stage` can classify a fetched `Transaction` without either
+/// one owning the other.
+#[inline(always)]
+fn is_likely_swap(tx: &Transaction) -> bool {
+    if tx.input.len() < 4 {
+        return false;
+    }
+
+    let selector = &tx.input[0..4];
+
+    // Common swap selectors
+    matches!(selector,
+        // UniswapV2
+        [0x38, 0xed, 0x17, 0x39] |  // swapExactTokensForTokens
+        [0x7f, 0xf3, 0x6a, 0xb5] |  // swapExactETHForTokens
+        [0x18, 0xcb, 0xaf, 0xe5] |  // swapExactTokensForETH
+        // UniswapV3
+        [0xc0, 0x4b, 0x8d, 0x59] |  // exactInput (SwapRouter, with deadline)
+        [0xb8, 0x58, 0x18, 0x3f] |  // exactInput (SwapRouter02)
+        [0x41, 0x4b, 0xf3, 0x89] |  // exactInputSingle (SwapRouter, with deadline)
+        // Universal Router
+        [0x36, 0x93, 0xd8, 0xa4] |  // execute
+        [0x24, 0x85, 0x6b, 0xc3]    // execute without deadline
+    ) || (selector[0] != 0x00)     // Must not start with 0x00
+}
+
+/// Fast swap parsing — real ABI decoding keyed on the matched selector.
+/// Returns `None` only when the calldata genuinely doesn't decode (too
+/// short, selector unrecognized, or a dynamic offset/length runs past
+/// the input); every other caller can trust `Some` as real swap data.
+fn parse_swap_fast(tx: &Transaction) -> Option<SwapInfo> {
+    if tx.input.len() < 4 {
+        return None;
+    }
+    let selector: [u8; 4] = tx.input[0..4].try_into().ok()?;
+    let args = &tx.input[4..];
+
+    match selector {
+        // swapExactTokensForTokens / swapExactTokensForETH:
+        // (uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)
+        [0x38, 0xed, 0x17, 0x39] | [0x18, 0xcb, 0xaf, 0xe5] => {
+            decode_v2_exact_in(args, tx.to)
         }
-        
-        let selector = &tx.input[0..4];
-        
-        // Common swap selectors
-        matches!(selector, 
-            // UniswapV2
-            [0x38, 0xed, 0x17, 0x39] |  // swapExactTokensForTokens
-            [0x7f, 0xf3, 0x6a, 0xb5] |  // swapExactETHForTokens
-            [0x18, 0xcb, 0xaf, 0xe5] |  // swapExactTokensForETH
-            // UniswapV3
-            [0xc0, 0x4b, 0x8d, 0x59] |  // exactInputSingle
-            [0xb8, 0x58, 0x18, 0x3f] |  // exactInput
-            [0x41, 0x4b, 0xf3, 0x89] |  // exactOutputSingle
-            // Universal Router
-            [0x36, 0x93, 0xd8, 0xa4] |  // execute
-            [0x24, 0x85, 0x6b, 0xc3]    // execute with deadline
-        ) || (selector[0] != 0x00)     // Must not start with 0x00
+        // swapExactETHForTokens: (uint256 amountOutMin, address[] path, address to, uint256 deadline)
+        [0x7f, 0xf3, 0x6a, 0xb5] => decode_v2_exact_eth_in(args, tx),
+        // SwapRouter02 exactInputSingle(ExactInputSingleParams)
+        [0x04, 0xe4, 0x5a, 0xaf] => decode_v3_exact_input_single(args),
+        // SwapRouter02 exactInput(ExactInputParams)
+        [0xb8, 0x58, 0x18, 0x3f] => decode_v3_exact_input(args),
+        // SwapRouter02 exactOutputSingle(ExactOutputSingleParams)
+        [0x50, 0x23, 0xb4, 0xdf] => decode_v3_exact_output_single(args),
+        // Universal Router execute(bytes commands, bytes[] inputs[, uint256 deadline])
+        [0x36, 0x93, 0xd8, 0xa4] | [0x24, 0x85, 0x6b, 0xc3] => decode_universal_router(args),
+        _ => None,
     }
-    
-    /// Fast swap parsing
-    fn parse_swap_fast(&self, tx: &Transaction) -> Option<SwapInfo> {
-        if tx.input.len() < 68 {
+}
+
+/// Reads calldata word `word_idx` (0-indexed, 32 bytes each) as a `U256`.
+fn read_u256(data: &[u8], word_idx: usize) -> Option<U256> {
+    let start = word_idx.checked_mul(32)?;
+    Some(U256::from_big_endian(data.get(start..start + 32)?))
+}
+
+/// Reads calldata word `word_idx` as a right-aligned `address`.
+fn read_address(data: &[u8], word_idx: usize) -> Option<Address> {
+    let start = word_idx.checked_mul(32)?;
+    Some(Address::from_slice(&data.get(start..start + 32)?[12..]))
+}
+
+/// Converts an ABI offset/length word to a byte index, rejecting anything
+/// that can't be a real offset into calldata this small — untrusted mempool
+/// input, so this must never panic the hot path via `U256::as_usize`.
+fn u256_to_index(value: U256) -> Option<usize> {
+    if value > U256::from(usize::MAX) {
+        return None;
+    }
+    Some(value.as_usize())
+}
+
+/// Reads a dynamic `bytes` argument given the index of its offset word
+/// (offset relative to the start of `data`).
+fn read_dynamic_bytes(data: &[u8], offset_word_idx: usize) -> Option<&[u8]> {
+    let offset = u256_to_index(read_u256(data, offset_word_idx)?)?;
+    if offset % 32 != 0 {
+        return None;
+    }
+    let len = u256_to_index(read_u256(data, offset / 32)?)?;
+    data.get(offset + 32..offset + 32 + len)
+}
+
+/// Reads a dynamic `bytes[]` argument given the index of its offset word.
+fn read_dynamic_bytes_array(data: &[u8], offset_word_idx: usize) -> Option<Vec<&[u8]>> {
+    let array_offset = u256_to_index(read_u256(data, offset_word_idx)?)?;
+    let array_data = data.get(array_offset..)?;
+    let length = u256_to_index(read_u256(array_data, 0)?)?;
+
+    let mut out = Vec::with_capacity(length);
+    for i in 0..length {
+        let elem_offset = u256_to_index(read_u256(array_data, 1 + i)?)?;
+        if elem_offset % 32 != 0 {
             return None;
         }
-        
-        // Simplified parsing - real implementation uses FFI
-        Some(SwapInfo {
-            token_in: Address::zero(),
-            token_out: Address::zero(),
-            amount_in: U256::zero(),
-            min_amount_out: U256::zero(),
-            dex_type: DexType::UniswapV2,
-            pool_address: Address::zero(),
-        })
+        let elem_data = array_data.get(elem_offset..)?;
+        let elem_len = u256_to_index(read_u256(elem_data, 0)?)?;
+        out.push(elem_data.get(32..32 + elem_len)?);
     }
-    
-    pub fn stop(&self) {
-        self.running.store(false, Ordering::SeqCst);
+    Some(out)
+}
+
+/// Reads a dynamic `address[] path` argument given the index of its offset
+/// word, returning `(first, last)` — the only two hops `SwapInfo` has room
+/// to record; any intermediate hops are collapsed into one logical swap,
+/// same as the rest of the pipeline treats a multi-hop route.
+fn read_address_path(data: &[u8], offset_word_idx: usize) -> Option<(Address, Address)> {
+    let offset = u256_to_index(read_u256(data, offset_word_idx)?)?;
+    if offset % 32 != 0 {
+        return None;
+    }
+    let start_word = offset / 32;
+    let length = u256_to_index(read_u256(data, start_word)?)?;
+    if length < 2 {
+        return None;
     }
+    let first = read_address(data, start_word + 1)?;
+    let last = read_address(data, start_word + length)?;
+    Some((first, last))
+}
+
+/// Steps a packed V3 multi-hop `path` (`token(20) | fee(3) | token(20) |
+/// fee(3) | ... | token(20)`) and returns the first and last token.
+fn decode_v3_path_ends(path: &[u8]) -> Option<(Address, Address)> {
+    const HOP: usize = 23; // 20-byte token + 3-byte fee
+    if path.len() < 20 || (path.len() - 20) % HOP != 0 {
+        return None;
+    }
+    let first = Address::from_slice(&path[0..20]);
+    let last_start = path.len() - 20;
+    Some((first, Address::from_slice(&path[last_start..last_start + 20])))
+}
+
+/// `CREATE2` init-code hash shared by UniswapV2 and its straight forks
+/// (SushiSwap). Camelot's AMM core isn't a V2 fork, so its pools aren't
+/// derivable this way and callers fall back to `Address::zero()`.
+const V2_PAIR_INIT_CODE_HASH: [u8; 32] = [
+    0x96, 0xe8, 0xac, 0x42, 0x77, 0x19, 0x8f, 0xf8, 0xb6, 0xf7, 0x85, 0x47, 0x8a, 0xa9, 0xa3, 0x9f,
+    0x40, 0x3c, 0xb7, 0x68, 0xdd, 0x02, 0xcb, 0xee, 0x32, 0x6c, 0x3e, 0x7d, 0xa3, 0x48, 0x88, 0x45,
+];
+
+/// Deterministic `CREATE2` pair address:
+/// `keccak256(0xff ++ factory ++ keccak256(token0 ++ token1) ++ init_code_hash)[12..]`,
+/// tokens sorted ascending the same way the factory itself requires.
+fn v2_pair_address(factory: Address, token_a: Address, token_b: Address) -> Address {
+    let (token0, token1) = if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+
+    let mut salt_input = [0u8; 40];
+    salt_input[..20].copy_from_slice(token0.as_bytes());
+    salt_input[20..].copy_from_slice(token1.as_bytes());
+    let salt = keccak256(salt_input);
+
+    let mut preimage = Vec::with_capacity(85);
+    preimage.push(0xff);
+    preimage.extend_from_slice(factory.as_bytes());
+    preimage.extend_from_slice(&salt);
+    preimage.extend_from_slice(&V2_PAIR_INIT_CODE_HASH);
+
+    Address::from_slice(&keccak256(preimage)[12..])
+}
+
+/// Resolves a V2-style router address to the `(DexType, factory)` pair
+/// needed to derive its pool addresses. SushiSwap is the only fork with a
+/// known router address wired up here; anything else (including the
+/// canonical Uniswap V2 router on chains where it exists) decodes with a
+/// generic `DexType::UniswapV2` and no derivable pool address, rather than
+/// guessing a factory for a router we don't actually recognize.
+fn v2_dex_for_router(router: Address) -> Option<(DexType, Address)> {
+    let sushi_router = Address::from_str("0x1b02dA8Cb0d097eB8D57A175b88c7D8b47997506").unwrap();
+    let sushi_factory = Address::from_str("0xc35DADB65012eC5796536bD9864eD8773aBc74C4").unwrap();
+    if router == sushi_router {
+        return Some((DexType::SushiSwap, sushi_factory));
+    }
+    None
+}
+
+fn v2_dex_and_pool(router: Option<Address>, token_in: Address, token_out: Address) -> (DexType, Address) {
+    router
+        .and_then(v2_dex_for_router)
+        .map(|(dex, factory)| (dex, v2_pair_address(factory, token_in, token_out)))
+        .unwrap_or((DexType::UniswapV2, Address::zero()))
+}
+
+/// Decodes `swapExactTokensForTokens`/`swapExactTokensForETH`-shaped
+/// calldata: `(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)`.
+fn decode_v2_exact_in(args: &[u8], router: Option<Address>) -> Option<SwapInfo> {
+    let amount_in = read_u256(args, 0)?;
+    let min_amount_out = read_u256(args, 1)?;
+    let (token_in, token_out) = read_address_path(args, 2)?;
+    let (dex_type, pool_address) = v2_dex_and_pool(router, token_in, token_out);
+
+    Some(SwapInfo { token_in, token_out, amount_in, min_amount_out, dex_type, pool_address })
+}
+
+/// Decodes `swapExactETHForTokens`-shaped calldata:
+/// `(uint256 amountOutMin, address[] path, address to, uint256 deadline)`,
+/// with `amountIn` coming from the transaction's `value` since the router
+/// takes ETH as `msg.value` rather than a calldata argument.
+fn decode_v2_exact_eth_in(args: &[u8], tx: &Transaction) -> Option<SwapInfo> {
+    let min_amount_out = read_u256(args, 0)?;
+    let (token_in, token_out) = read_address_path(args, 1)?;
+    let (dex_type, pool_address) = v2_dex_and_pool(tx.to, token_in, token_out);
+
+    Some(SwapInfo { token_in, token_out, amount_in: tx.value, min_amount_out, dex_type, pool_address })
+}
+
+/// Decodes `exactInputSingle(ExactInputSingleParams)` using the
+/// SwapRouter02 layout (no `deadline` field): `(tokenIn, tokenOut, fee,
+/// recipient, amountIn, amountOutMinimum, sqrtPriceLimitX96)`. Every field
+/// is fixed-size, so the struct is inlined with no offset pointer.
+fn decode_v3_exact_input_single(args: &[u8]) -> Option<SwapInfo> {
+    let token_in = read_address(args, 0)?;
+    let token_out = read_address(args, 1)?;
+    let amount_in = read_u256(args, 4)?;
+    let min_amount_out = read_u256(args, 5)?;
+
+    Some(SwapInfo {
+        token_in,
+        token_out,
+        amount_in,
+        min_amount_out,
+        dex_type: DexType::UniswapV3,
+        // Deriving a V3 pool address needs `factory.getPool(token0, token1,
+        // fee)`; out of reach for this synchronous hot-path parser.
+        pool_address: Address::zero(),
+    })
+}
+
+/// Decodes `exactInput(ExactInputParams)` using the SwapRouter02 layout
+/// (no `deadline` field): `(bytes path, address recipient, uint256
+/// amountIn, uint256 amountOutMinimum)`. The struct carries a dynamic field
+/// so it's itself encoded as an offset pointer (the sole argument).
+fn decode_v3_exact_input(args: &[u8]) -> Option<SwapInfo> {
+    let tuple_start = u256_to_index(read_u256(args, 0)?)?;
+    if tuple_start % 32 != 0 {
+        return None;
+    }
+    let tuple = args.get(tuple_start..)?;
+
+    let amount_in = read_u256(tuple, 1)?;
+    let min_amount_out = read_u256(tuple, 2)?;
+    let path = read_dynamic_bytes(tuple, 0)?;
+    let (token_in, token_out) = decode_v3_path_ends(path)?;
+
+    Some(SwapInfo {
+        token_in,
+        token_out,
+        amount_in,
+        min_amount_out,
+        dex_type: DexType::UniswapV3,
+        pool_address: Address::zero(),
+    })
+}
+
+/// Decodes `exactOutputSingle(ExactOutputSingleParams)` using the
+/// SwapRouter02 layout: `(tokenIn, tokenOut, fee, recipient, amountOut,
+/// amountInMaximum, sqrtPriceLimitX96)`. There's no "min out" for an
+/// exact-output swap, so `amount_in`/`min_amount_out` carry the worst-case
+/// input and the guaranteed exact output respectively.
+fn decode_v3_exact_output_single(args: &[u8]) -> Option<SwapInfo> {
+    let token_in = read_address(args, 0)?;
+    let token_out = read_address(args, 1)?;
+    let amount_out = read_u256(args, 4)?;
+    let amount_in_maximum = read_u256(args, 5)?;
+
+    Some(SwapInfo {
+        token_in,
+        token_out,
+        amount_in: amount_in_maximum,
+        min_amount_out: amount_out,
+        dex_type: DexType::UniswapV3,
+        pool_address: Address::zero(),
+    })
+}
+
+/// Universal Router command ids this parser understands — the low 5 bits
+/// of each command byte (bit 7 is the `ALLOW_REVERT` flag, bit 6 unused).
+const CMD_V3_SWAP_EXACT_IN: u8 = 0x00;
+const CMD_V2_SWAP_EXACT_IN: u8 = 0x08;
+
+/// Decodes Universal Router `execute(bytes commands, bytes[] inputs, ...)`
+/// by iterating the command byte array and decoding the first recognized
+/// V2/V3 swap sub-command's input. Later swap commands in the same bundle
+/// (if any) are ignored — `SwapInfo` only has room for one logical swap,
+/// same as every other selector handled here.
+fn decode_universal_router(args: &[u8]) -> Option<SwapInfo> {
+    let commands = read_dynamic_bytes(args, 0)?;
+    let inputs = read_dynamic_bytes_array(args, 1)?;
+
+    for (i, &command) in commands.iter().enumerate() {
+        let Some(&input) = inputs.get(i) else { continue };
+        match command & 0x1f {
+            CMD_V2_SWAP_EXACT_IN => {
+                // (address recipient, uint256 amountIn, uint256 amountOutMin, address[] path, bool payerIsUser)
+                let amount_in = read_u256(input, 1)?;
+                let min_amount_out = read_u256(input, 2)?;
+                if let Some((token_in, token_out)) = read_address_path(input, 3) {
+                    return Some(SwapInfo {
+                        token_in,
+                        token_out,
+                        amount_in,
+                        min_amount_out,
+                        dex_type: DexType::UniswapV2,
+                        pool_address: Address::zero(),
+                    });
+                }
+            }
+            CMD_V3_SWAP_EXACT_IN => {
+                // (address recipient, uint256 amountIn, uint256 amountOutMin, bytes path, bool payerIsUser)
+                let amount_in = read_u256(input, 1)?;
+                let min_amount_out = read_u256(input, 2)?;
+                if let Some((token_in, token_out)) =
+                    read_dynamic_bytes(input, 3).and_then(decode_v3_path_ends)
+                {
+                    return Some(SwapInfo {
+                        token_in,
+                        token_out,
+                        amount_in,
+                        min_amount_out,
+                        dex_type: DexType::UniswapV3,
+                        pool_address: Address::zero(),
+                    });
+                }
+            }
+            _ => continue,
+        }
+    }
+
+    None
 }
 
 /// Swap info for mempool parsing
@@ -0,0 +1,236 @@
+//! EVM provider abstraction
+//!
+//! Executor/simulator/mempool code used to be hard-wired to `Provider<Http>`,
+//! re-signing and re-clienting on every call. `EvmProvider` is the minimal set
+//! of JSON-RPC operations the engine actually needs, so a backend (HTTP, WS,
+//! or the metrics/retry middleware below) can be swapped in behind `Arc<dyn
+//! EvmProvider>` without touching call sites.
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::config::RpcConfig;
+
+/// Minimal set of JSON-RPC calls the MEV engine needs from an EVM node.
+#[async_trait]
+pub trait EvmProvider: Send + Sync {
+    async fn get_gas_price(&self) -> anyhow::Result<U256>;
+    async fn estimate_gas(&self, tx: &TypedTransaction) -> anyhow::Result<U256>;
+    async fn call(&self, tx: &TypedTransaction) -> anyhow::Result<Bytes>;
+    async fn send_raw_transaction(&self, raw: Bytes) -> anyhow::Result<TxHash>;
+    async fn get_block(&self, block: BlockNumber) -> anyhow::Result<Option<Block<TxHash>>>;
+}
+
+/// HTTP-backed implementation.
+pub struct HttpEvmProvider {
+    inner: Provider<Http>,
+}
+
+impl HttpEvmProvider {
+    pub fn new(url: &str) -> anyhow::Result<Self> {
+        Ok(Self { inner: Provider::<Http>::try_from(url)? })
+    }
+}
+
+#[async_trait]
+impl EvmProvider for HttpEvmProvider {
+    async fn get_gas_price(&self) -> anyhow::Result<U256> {
+        Ok(self.inner.get_gas_price().await?)
+    }
+
+    async fn estimate_gas(&self, tx: &TypedTransaction) -> anyhow::Result<U256> {
+        Ok(self.inner.estimate_gas(tx, None).await?)
+    }
+
+    async fn call(&self, tx: &TypedTransaction) -> anyhow::Result<Bytes> {
+        Ok(self.inner.call(tx, None).await?)
+    }
+
+    async fn send_raw_transaction(&self, raw: Bytes) -> anyhow::Result<TxHash> {
+        Ok(self.inner.send_raw_transaction(raw).await?.tx_hash())
+    }
+
+    async fn get_block(&self, block: BlockNumber) -> anyhow::Result<Option<Block<TxHash>>> {
+        Ok(self.inner.get_block(block).await?)
+    }
+}
+
+/// WebSocket-backed implementation — same semantics over a persistent connection.
+pub struct WsEvmProvider {
+    inner: Provider<Ws>,
+}
+
+impl WsEvmProvider {
+    pub async fn connect(url: &str) -> anyhow::Result<Self> {
+        Ok(Self { inner: Provider::<Ws>::connect(url).await? })
+    }
+}
+
+#[async_trait]
+impl EvmProvider for WsEvmProvider {
+    async fn get_gas_price(&self) -> anyhow::Result<U256> {
+        Ok(self.inner.get_gas_price().await?)
+    }
+
+    async fn estimate_gas(&self, tx: &TypedTransaction) -> anyhow::Result<U256> {
+        Ok(self.inner.estimate_gas(tx, None).await?)
+    }
+
+    async fn call(&self, tx: &TypedTransaction) -> anyhow::Result<Bytes> {
+        Ok(self.inner.call(tx, None).await?)
+    }
+
+    async fn send_raw_transaction(&self, raw: Bytes) -> anyhow::Result<TxHash> {
+        Ok(self.inner.send_raw_transaction(raw).await?.tx_hash())
+    }
+
+    async fn get_block(&self, block: BlockNumber) -> anyhow::Result<Option<Block<TxHash>>> {
+        Ok(self.inner.get_block(block).await?)
+    }
+}
+
+/// Call/error counters and cumulative latency for one RPC method.
+#[derive(Default)]
+struct MethodMetrics {
+    calls: AtomicU64,
+    errors: AtomicU64,
+    total_latency_ns: AtomicU64,
+}
+
+/// Point-in-time snapshot of `MethodMetrics` for a single RPC method.
+#[derive(Debug, Clone, Copy)]
+pub struct MethodStats {
+    pub calls: u64,
+    pub errors: u64,
+    pub avg_latency_ms: f64,
+}
+
+/// Wraps one or more `EvmProvider`s with `RpcConfig`'s `retry_count` and
+/// `request_timeout_ms`, failing over from `providers[0]` to the rest in
+/// order, and records per-method call/error counts and cumulative latency
+/// along the way.
+pub struct MetricsRetryMiddleware {
+    providers: Vec<Arc<dyn EvmProvider>>,
+    retry_count: u32,
+    request_timeout: Duration,
+    metrics: DashMap<&'static str, MethodMetrics>,
+}
+
+impl MetricsRetryMiddleware {
+    pub fn new(providers: Vec<Arc<dyn EvmProvider>>, rpc: &RpcConfig) -> Self {
+        Self {
+            providers,
+            retry_count: rpc.retry_count,
+            request_timeout: Duration::from_millis(rpc.request_timeout_ms),
+            metrics: DashMap::new(),
+        }
+    }
+
+    /// Build straight from `Config::rpc`: `primary` endpoints are tried first
+    /// in order, then `fallback` — the two endpoint lists that existed in
+    /// `Config` without anything actually reading them.
+    pub fn from_config(rpc: &RpcConfig) -> anyhow::Result<Self> {
+        let urls: Vec<&String> = rpc.primary.iter().chain(rpc.fallback.iter()).collect();
+        if urls.is_empty() {
+            anyhow::bail!("RpcConfig has no primary or fallback endpoints configured");
+        }
+
+        let providers = urls
+            .into_iter()
+            .map(|url| HttpEvmProvider::new(url).map(|p| Arc::new(p) as Arc<dyn EvmProvider>))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self::new(providers, rpc))
+    }
+
+    /// Current call/error/latency stats for `method`, if it's been called at least once.
+    pub fn method_stats(&self, method: &str) -> Option<MethodStats> {
+        self.metrics.get(method).map(|m| {
+            let calls = m.calls.load(Ordering::Relaxed);
+            let total_ns = m.total_latency_ns.load(Ordering::Relaxed);
+            MethodStats {
+                calls,
+                errors: m.errors.load(Ordering::Relaxed),
+                avg_latency_ms: if calls == 0 { 0.0 } else { (total_ns as f64 / calls as f64) / 1_000_000.0 },
+            }
+        })
+    }
+
+    /// Try each provider in order, retrying each up to `retry_count` times
+    /// under `request_timeout`, recording latency/errors per attempt.
+    async fn call_with_retry<T, F, Fut>(&self, method: &'static str, f: F) -> anyhow::Result<T>
+    where
+        F: Fn(Arc<dyn EvmProvider>) -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        let mut last_err = None;
+
+        for provider in &self.providers {
+            for attempt in 0..=self.retry_count {
+                let start = Instant::now();
+                let outcome = tokio::time::timeout(self.request_timeout, f(provider.clone())).await;
+                let elapsed = start.elapsed();
+
+                let metrics = self.metrics.entry(method).or_insert_with(MethodMetrics::default);
+                metrics.calls.fetch_add(1, Ordering::Relaxed);
+                metrics.total_latency_ns.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+
+                match outcome {
+                    Ok(Ok(value)) => return Ok(value),
+                    Ok(Err(e)) => {
+                        metrics.errors.fetch_add(1, Ordering::Relaxed);
+                        warn!("{} attempt {} failed: {}", method, attempt, e);
+                        last_err = Some(e);
+                    }
+                    Err(_) => {
+                        metrics.errors.fetch_add(1, Ordering::Relaxed);
+                        warn!("{} attempt {} timed out after {:?}", method, attempt, self.request_timeout);
+                        last_err = Some(anyhow::anyhow!("{} timed out", method));
+                    }
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("{} failed: no providers configured", method)))
+    }
+}
+
+#[async_trait]
+impl EvmProvider for MetricsRetryMiddleware {
+    async fn get_gas_price(&self) -> anyhow::Result<U256> {
+        self.call_with_retry("get_gas_price", |p| async move { p.get_gas_price().await }).await
+    }
+
+    async fn estimate_gas(&self, tx: &TypedTransaction) -> anyhow::Result<U256> {
+        let tx = tx.clone();
+        self.call_with_retry("estimate_gas", move |p| {
+            let tx = tx.clone();
+            async move { p.estimate_gas(&tx).await }
+        }).await
+    }
+
+    async fn call(&self, tx: &TypedTransaction) -> anyhow::Result<Bytes> {
+        let tx = tx.clone();
+        self.call_with_retry("call", move |p| {
+            let tx = tx.clone();
+            async move { p.call(&tx).await }
+        }).await
+    }
+
+    async fn send_raw_transaction(&self, raw: Bytes) -> anyhow::Result<TxHash> {
+        self.call_with_retry("send_raw_transaction", move |p| {
+            let raw = raw.clone();
+            async move { p.send_raw_transaction(raw).await }
+        }).await
+    }
+
+    async fn get_block(&self, block: BlockNumber) -> anyhow::Result<Option<Block<TxHash>>> {
+        self.call_with_retry("get_block", move |p| async move { p.get_block(block).await }).await
+    }
+}
@@ -0,0 +1,196 @@
+//! Scored, readiness-aware opportunity queue
+//!
+//! Sits between opportunity detection and bundle building. Several
+//! detectors can independently produce opportunities against the same
+//! pending transaction (e.g. a sandwich's linked frontrun/backrun pair
+//! alongside a plain arbitrage hit), so rather than submitting whichever
+//! arrives first, everything lands here to be ranked, capped, and checked
+//! for staleness before `BundleBuilder` ever sees it.
+
+use crate::types::Opportunity;
+use std::sync::Mutex;
+
+/// Scores an opportunity for queue ordering — higher is better. Swappable
+/// so strategies beyond the default (e.g. profit minus estimated revert
+/// risk) can be substituted without touching the queue internals.
+pub trait OpportunityScorer: Send + Sync {
+    fn score(&self, opportunity: &Opportunity) -> f64;
+}
+
+/// Default scorer: expected profit per unit of gas, so a cheaper win is
+/// preferred over a pricier one of similar size.
+pub struct ProfitPerGasScorer;
+
+impl OpportunityScorer for ProfitPerGasScorer {
+    fn score(&self, opportunity: &Opportunity) -> f64 {
+        if opportunity.gas_estimate == 0 {
+            return 0.0;
+        }
+        opportunity.expected_profit as f64 / opportunity.gas_estimate as f64
+    }
+}
+
+struct Entry {
+    opportunity: Opportunity,
+    score: f64,
+}
+
+/// Ranks pending opportunities by a pluggable `OpportunityScorer`, enforces
+/// a per-`target_tx` cap so one victim transaction can't flood the queue,
+/// and only ever hands out entries whose `deadline` hasn't passed yet.
+pub struct OpportunityQueue {
+    scorer: Box<dyn OpportunityScorer>,
+    max_per_target: usize,
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl OpportunityQueue {
+    /// Queue with the default `ProfitPerGasScorer`.
+    pub fn new(max_per_target: usize) -> Self {
+        Self::with_scorer(max_per_target, Box::new(ProfitPerGasScorer))
+    }
+
+    pub fn with_scorer(max_per_target: usize, scorer: Box<dyn OpportunityScorer>) -> Self {
+        Self {
+            scorer,
+            max_per_target: max_per_target.max(1),
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Scores and inserts `opportunity`. If its `target_tx` already has
+    /// `max_per_target` entries queued, the new one only gets in by beating
+    /// the worst-scored entry for that target, which is evicted to make
+    /// room; otherwise it's dropped.
+    pub fn push(&self, opportunity: Opportunity) {
+        let score = self.scorer.score(&opportunity);
+        let mut entries = self.entries.lock().unwrap();
+
+        if let Some(target) = opportunity.target_tx {
+            let same_target: Vec<usize> = entries
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.opportunity.target_tx == Some(target))
+                .map(|(i, _)| i)
+                .collect();
+
+            if same_target.len() >= self.max_per_target {
+                let worst = same_target
+                    .iter()
+                    .copied()
+                    .min_by(|&a, &b| entries[a].score.total_cmp(&entries[b].score));
+
+                match worst {
+                    Some(idx) if entries[idx].score < score => {
+                        entries.remove(idx);
+                    }
+                    _ => return,
+                }
+            }
+        }
+
+        entries.push(Entry { opportunity, score });
+    }
+
+    /// Removes and returns the highest-scored opportunity that's still
+    /// ready (`deadline >= current_block`), or `None` if nothing queued is
+    /// ready yet.
+    pub fn pop_best(&self, current_block: u64) -> Option<Opportunity> {
+        let mut entries = self.entries.lock().unwrap();
+        let best_idx = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.opportunity.deadline >= current_block)
+            .max_by(|(_, a), (_, b)| a.score.total_cmp(&b.score))
+            .map(|(i, _)| i)?;
+        Some(entries.remove(best_idx).opportunity)
+    }
+
+    /// Drops every entry whose `deadline` has already passed as of
+    /// `current_block`. Call once per new block so the queue doesn't carry
+    /// opportunities that can never be submitted anymore. Returns the
+    /// number of entries dropped.
+    pub fn prune(&self, current_block: u64) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|e| e.opportunity.deadline >= current_block);
+        before - entries.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OpportunityType;
+
+    fn opp(target_tx: u8, expected_profit: u128, gas_estimate: u64, deadline: u64) -> Opportunity {
+        Opportunity {
+            opportunity_type: OpportunityType::Arbitrage,
+            token_in: "in".to_string(),
+            token_out: "out".to_string(),
+            amount_in: 0,
+            expected_profit,
+            gas_estimate,
+            deadline,
+            path: vec![],
+            target_tx: Some([target_tx; 32]),
+        }
+    }
+
+    #[test]
+    fn pop_best_returns_highest_score_first() {
+        let queue = OpportunityQueue::new(10);
+        queue.push(opp(1, 100, 100_000, 100));
+        queue.push(opp(2, 500, 100_000, 100));
+        queue.push(opp(3, 200, 100_000, 100));
+
+        let best = queue.pop_best(0).unwrap();
+        assert_eq!(best.expected_profit, 500);
+        let next = queue.pop_best(0).unwrap();
+        assert_eq!(next.expected_profit, 200);
+    }
+
+    #[test]
+    fn per_target_cap_evicts_the_worst_entry() {
+        let queue = OpportunityQueue::new(2);
+        queue.push(opp(1, 100, 100_000, 100));
+        queue.push(opp(1, 200, 100_000, 100));
+        // Already at the cap for target 1; this one scores worse than both
+        // existing entries, so it's dropped rather than evicting anything.
+        queue.push(opp(1, 50, 100_000, 100));
+        assert_eq!(queue.len(), 2);
+
+        // Scores higher than the worst queued entry for target 1, so it
+        // evicts that one instead.
+        queue.push(opp(1, 300, 100_000, 100));
+        assert_eq!(queue.len(), 2);
+        let best = queue.pop_best(0).unwrap();
+        assert_eq!(best.expected_profit, 300);
+    }
+
+    #[test]
+    fn prune_drops_stale_deadlines_and_pop_best_skips_them() {
+        let queue = OpportunityQueue::new(10);
+        queue.push(opp(1, 500, 100_000, 10)); // deadline already passed
+        queue.push(opp(2, 100, 100_000, 100)); // still ready
+
+        // pop_best at block 50 must skip the stale entry for target 1 and
+        // return the still-ready one for target 2.
+        let best = queue.pop_best(50).unwrap();
+        assert_eq!(best.expected_profit, 100);
+
+        // The stale entry for target 1 is still queued until pruned.
+        assert_eq!(queue.len(), 1);
+        let dropped = queue.prune(50);
+        assert_eq!(dropped, 1);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+//! Synthetic mempool replay / load-generator mode
+//!
+//! Feeds either pre-recorded swap calldata or randomly generated,
+//! realistically-shaped V2/V3 swap calldata through `ArbitrageDetector::detect`
+//! at a controlled rate, ramping the rate up in fixed steps so operators can
+//! find the throughput knee where detection starts falling behind — without
+//! needing a live WebSocket mempool feed.
+
+use crate::config::Config;
+use crate::detector::ArbitrageDetector;
+use crate::types::PendingTx;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Where replay transactions come from.
+pub enum ReplaySource {
+    /// One hex-encoded (`0x`-prefixed or not) transaction calldata per line.
+    File(PathBuf),
+    /// Randomly generated V2/V3 swap calldata.
+    Synthetic,
+}
+
+/// Rate ramp schedule: start at `start_tps`, step up by `step_tps` every
+/// `step_duration`, capping at `max_tps`.
+pub struct ReplayConfig {
+    pub source: ReplaySource,
+    pub start_tps: u64,
+    pub step_tps: u64,
+    pub max_tps: u64,
+    pub step_duration: Duration,
+}
+
+impl ReplayConfig {
+    pub fn synthetic(start_tps: u64) -> Self {
+        let start_tps = start_tps.max(1);
+        Self {
+            source: ReplaySource::Synthetic,
+            start_tps,
+            step_tps: start_tps,
+            max_tps: start_tps * 10,
+            step_duration: Duration::from_secs(10),
+        }
+    }
+
+    pub fn from_file(path: PathBuf, start_tps: u64) -> Self {
+        Self {
+            source: ReplaySource::File(path),
+            ..Self::synthetic(start_tps)
+        }
+    }
+}
+
+/// Detection latency samples and counters collected during a replay run.
+/// Percentiles are computed by sorting at report time rather than via a
+/// bucketed histogram — replay is a one-shot CLI measurement, not the
+/// ingest hot path `MempoolStats` serves, so the extra precision is worth
+/// the allocation.
+#[derive(Default)]
+struct ReplayStats {
+    latencies_ns: Mutex<Vec<u64>>,
+    opportunities_found: AtomicU64,
+    txs_processed: AtomicU64,
+}
+
+impl ReplayStats {
+    fn record(&self, latency_ns: u64, found_opportunity: bool) {
+        self.latencies_ns.lock().unwrap().push(latency_ns);
+        self.txs_processed.fetch_add(1, Ordering::Relaxed);
+        if found_opportunity {
+            self.opportunities_found.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Summary printed at shutdown.
+pub struct ReplayReport {
+    pub txs_processed: u64,
+    pub opportunities_found: u64,
+    pub wall_time: Duration,
+    pub p50_ns: u64,
+    pub p90_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+}
+
+impl ReplayReport {
+    pub fn print_summary(&self) {
+        let rate = self.txs_processed as f64 / self.wall_time.as_secs_f64().max(1e-9);
+        let opp_rate = self.opportunities_found as f64 / self.wall_time.as_secs_f64().max(1e-9);
+        info!("📼 Replay complete");
+        info!("   TXs processed:        {}", self.txs_processed);
+        info!("   Opportunities found:  {} ({:.2}/s)", self.opportunities_found, opp_rate);
+        info!("   Throughput:           {:.1} tx/s", rate);
+        info!(
+            "   Detection latency:    p50={:.1}µs p90={:.1}µs p99={:.1}µs max={:.1}µs",
+            self.p50_ns as f64 / 1000.0,
+            self.p90_ns as f64 / 1000.0,
+            self.p99_ns as f64 / 1000.0,
+            self.max_ns as f64 / 1000.0,
+        );
+    }
+}
+
+/// Runs the replay/load-generator against a fresh `ArbitrageDetector`,
+/// ramping the feed rate per `config`'s schedule and timing every
+/// `detect()` call.
+pub async fn run(config: ReplayConfig, app_config: Arc<Config>) -> anyhow::Result<ReplayReport> {
+    let detector = ArbitrageDetector::new(app_config);
+    let stats = Arc::new(ReplayStats::default());
+
+    let mut rng = Xorshift64::new(0x9E3779B97F4A7C15);
+    let mut file_lines = match &config.source {
+        ReplaySource::File(path) => Some(BufReader::new(std::fs::File::open(path)?).lines()),
+        ReplaySource::Synthetic => None,
+    };
+
+    let start = Instant::now();
+    let mut rate = config.start_tps;
+
+    'ramp: loop {
+        info!("Replay ramping to {} tx/s", rate);
+        let interval = Duration::from_secs_f64(1.0 / rate as f64);
+        let step_deadline = Instant::now() + config.step_duration;
+        let mut next_tick = Instant::now();
+
+        while Instant::now() < step_deadline {
+            let tx = match &mut file_lines {
+                Some(lines) => match lines.next() {
+                    Some(Ok(line)) => match parse_hex_tx(&line) {
+                        Some(tx) => tx,
+                        None => continue,
+                    },
+                    Some(Err(_)) => continue,
+                    None => break 'ramp, // file exhausted
+                },
+                None => synthetic_tx(&mut rng),
+            };
+
+            let detect_start = Instant::now();
+            let found = detector.detect(&tx).await.is_some();
+            stats.record(detect_start.elapsed().as_nanos() as u64, found);
+
+            next_tick += interval;
+            let now = Instant::now();
+            if next_tick > now {
+                tokio::time::sleep(next_tick - now).await;
+            }
+        }
+
+        if rate >= config.max_tps {
+            break;
+        }
+        rate = (rate + config.step_tps).min(config.max_tps);
+    }
+
+    let wall_time = start.elapsed();
+    let mut latencies = stats.latencies_ns.lock().unwrap().clone();
+    latencies.sort_unstable();
+    let percentile = |p: f64| -> u64 {
+        if latencies.is_empty() {
+            return 0;
+        }
+        let idx = ((latencies.len() as f64 - 1.0) * p).round() as usize;
+        latencies[idx]
+    };
+
+    Ok(ReplayReport {
+        txs_processed: stats.txs_processed.load(Ordering::Relaxed),
+        opportunities_found: stats.opportunities_found.load(Ordering::Relaxed),
+        wall_time,
+        p50_ns: percentile(0.50),
+        p90_ns: percentile(0.90),
+        p99_ns: percentile(0.99),
+        max_ns: latencies.last().copied().unwrap_or(0),
+    })
+}
+
+/// Decodes one line of a replay file as `0x`-prefixed (or bare) hex
+/// calldata, wrapping it in a placeholder `PendingTx` aimed at a router
+/// address so `ArbitrageDetector::detect`'s calldata parsing runs
+/// unmodified.
+fn parse_hex_tx(line: &str) -> Option<PendingTx> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    let hex_str = line.strip_prefix("0x").unwrap_or(line);
+    let input = hex::decode(hex_str).ok()?;
+
+    Some(PendingTx {
+        hash: [0u8; 32],
+        from: [0u8; 20],
+        to: Some([0u8; 20]),
+        value: 0,
+        gas_price: 50_000_000_000,
+        gas_limit: 300_000,
+        input,
+        nonce: 0,
+        timestamp: now_secs(),
+    })
+}
+
+/// Builds one randomly generated V2 or V3 swap transaction, with calldata
+/// shaped so `ArbitrageDetector`'s existing selector-matched decoding
+/// (`swap_parse::parse_swap`) accepts it: real selectors, random but
+/// well-formed token addresses, and amounts in a realistic 0.1-50 ETH
+/// range.
+fn synthetic_tx(rng: &mut Xorshift64) -> PendingTx {
+    let token_in = random_address(rng);
+    let token_out = random_address(rng);
+    let amount_in: u128 = 100_000_000_000_000_000 + (rng.next_u64() as u128 % 50_000_000_000_000_000_000);
+
+    let input = if rng.next_u64() % 2 == 0 {
+        build_v2_calldata(&token_in, &token_out, amount_in)
+    } else {
+        build_v3_calldata(&token_in, &token_out, amount_in)
+    };
+
+    let mut hash = [0u8; 32];
+    for b in hash.iter_mut() {
+        *b = (rng.next_u64() & 0xff) as u8;
+    }
+
+    PendingTx {
+        hash,
+        from: random_address(rng),
+        to: Some(random_address(rng)),
+        value: 0,
+        gas_price: 50_000_000_000,
+        gas_limit: 300_000,
+        input,
+        nonce: rng.next_u64(),
+        timestamp: now_secs(),
+    }
+}
+
+fn random_address(rng: &mut Xorshift64) -> [u8; 20] {
+    let mut addr = [0u8; 20];
+    for b in addr.iter_mut() {
+        *b = (rng.next_u64() & 0xff) as u8;
+    }
+    addr
+}
+
+/// `swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin,
+/// address[] path, address to, uint256 deadline)`, properly ABI-encoded
+/// (dynamic `path` offset-pointed, 2-hop `[token_in, token_out]`).
+fn build_v2_calldata(token_in: &[u8; 20], token_out: &[u8; 20], amount_in: u128) -> Vec<u8> {
+    let amount_out_min = amount_in / 100; // 1% slippage tolerance
+    let mut data = vec![0x38, 0xed, 0x17, 0x39];
+    data.extend_from_slice(&u256_word(amount_in));
+    data.extend_from_slice(&u256_word(amount_out_min));
+    data.extend_from_slice(&u256_word(160)); // path offset: 5 head words * 32
+    data.extend_from_slice(&address_word(&[0u8; 20])); // to (recipient)
+    data.extend_from_slice(&u256_word(u64::MAX as u128)); // deadline
+    data.extend_from_slice(&u256_word(2)); // path.length
+    data.extend_from_slice(&address_word(token_in));
+    data.extend_from_slice(&address_word(token_out));
+    data
+}
+
+fn u256_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..32].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn address_word(addr: &[u8; 20]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..32].copy_from_slice(addr);
+    word
+}
+
+/// `exactInputSingle(ExactInputSingleParams)` — matches
+/// `swap_parse::parse_v3_swap`'s offsets: `token_in`/`token_out` at
+/// `args[12..32]`/`args[44..64]`, fee in the low 3 bytes of the third
+/// word, `amount_in`/`amount_out_min` at `args[176..192]`/`args[208..224]`.
+fn build_v3_calldata(token_in: &[u8; 20], token_out: &[u8; 20], amount_in: u128) -> Vec<u8> {
+    let mut data = vec![0x41, 0x4b, 0xf3, 0x89];
+    let mut args = vec![0u8; 8 * 32];
+    args[12..32].copy_from_slice(token_in);
+    args[44..64].copy_from_slice(token_out);
+    args[93..96].copy_from_slice(&3000u32.to_be_bytes()[1..4]); // 0.3% fee tier
+    args[176..192].copy_from_slice(&amount_in.to_be_bytes());
+    let amount_out_min = amount_in / 100; // 1% slippage tolerance
+    args[208..224].copy_from_slice(&amount_out_min.to_be_bytes());
+    data.extend_from_slice(&args);
+    data
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Minimal deterministic PRNG (xorshift64*) — avoids pulling in a new
+/// dependency just to generate plausible-looking addresses/amounts for the
+/// load generator.
+struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed | 1 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+}
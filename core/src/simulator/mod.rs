@@ -1,15 +1,34 @@
 //! EVM Simulator module
+//!
+//! `simulate_bundle` used to echo a flat 100k gas per tx and report zero
+//! profit without ever touching an EVM. `ForkDb` forks live chain state over
+//! an ethers `Provider` at a chosen block (an `AlloyDB`-style backend: reads
+//! that miss its in-memory cache block on an RPC round-trip, then are
+//! cached so later reads in the same simulation are free) and each
+//! `BundleTransaction` executes against the same in-memory EVM in sequence,
+//! so later transactions observe earlier ones' writes.
 
 use crate::config::Config;
-use crate::types::{Opportunity, SimulationResult, Bundle};
-use std::sync::Arc;
+use crate::types::{Bundle, BundleTransaction, Opportunity, SimulationResult, StateChange};
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::{Address as EthAddress, BlockId, BlockNumber, H256 as EthH256, U256 as EthU256};
+use revm::primitives::{
+    AccountInfo, Address as RevmAddress, Bytecode, ExecutionResult, TransactTo, B256,
+    U256 as RevmU256,
+};
+use revm::{Database, DatabaseCommit, Evm};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tracing::{debug, warn};
 
 /// EVM Simulator for transaction simulation
 pub struct EvmSimulator {
     config: Arc<Config>,
     count: AtomicU64,
+    provider: Option<Arc<Provider<Http>>>,
+    fork_block: Option<u64>,
+    searcher_address: Option<EthAddress>,
 }
 
 impl EvmSimulator {
@@ -17,9 +36,26 @@ impl EvmSimulator {
         Self {
             config,
             count: AtomicU64::new(0),
+            provider: None,
+            fork_block: None,
+            searcher_address: None,
         }
     }
 
+    /// Point the simulator at a live RPC to fork state from, at `fork_block`
+    /// (or the latest block if `None`). Without this, `simulate_bundle` can't
+    /// actually execute anything and reports an error instead of guessing.
+    pub fn set_provider(&mut self, provider: Arc<Provider<Http>>, fork_block: Option<u64>) {
+        self.provider = Some(provider);
+        self.fork_block = fork_block;
+    }
+
+    /// The address whose balance delta across the bundle is reported as
+    /// `SimulationResult.profit`.
+    pub fn set_searcher_address(&mut self, address: EthAddress) {
+        self.searcher_address = Some(address);
+    }
+
     pub async fn start(&self) -> anyhow::Result<()> {
         Ok(())
     }
@@ -52,33 +88,30 @@ impl EvmSimulator {
     pub async fn simulate_bundle(&self, bundle: &Bundle) -> SimulationResult {
         self.count.fetch_add(1, Ordering::Relaxed);
 
-        // Simulate all transactions in sequence
-        let mut total_profit = 0i128;
-        let mut total_gas = 0u64;
-
-        for tx in &bundle.transactions {
-            // TODO: Simulate each transaction using revm
-            total_gas += 100_000; // Placeholder
-        }
-
-        SimulationResult {
-            success: true,
-            profit: total_profit,
-            gas_used: total_gas,
-            error: None,
-            state_changes: vec![],
+        match self.run_bundle(bundle).await {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Bundle simulation failed: {}", e);
+                SimulationResult {
+                    success: false,
+                    profit: 0,
+                    gas_used: 0,
+                    error: Some(e.to_string()),
+                    state_changes: vec![],
+                }
+            }
         }
     }
 
     async fn run_simulation(&self, opportunity: &Opportunity) -> anyhow::Result<SimulationResult> {
-        // TODO: Implement revm simulation
-        // 1. Fork current state
-        // 2. Execute opportunity transaction
-        // 3. Check profit
-
+        // `Opportunity` doesn't carry executable calldata (just token
+        // symbols and a DEX path), so there's nothing for revm to actually
+        // run here. Bundle-level simulation (`simulate_bundle`, below) is
+        // where real execution happens against forked state; this stays a
+        // profit-estimate pass-through until opportunities carry a concrete
+        // transaction to simulate.
         debug!("Simulating opportunity: {:?}", opportunity.opportunity_type);
 
-        // Placeholder implementation
         let simulated_profit = opportunity.expected_profit as i128;
         let gas_used = opportunity.gas_estimate;
 
@@ -91,11 +124,319 @@ impl EvmSimulator {
         })
     }
 
+    /// Fork state at `self.fork_block` and execute `bundle.transactions`
+    /// sequentially against the same in-memory EVM, so later transactions
+    /// see earlier writes. Reports the searcher's realized balance delta as
+    /// `profit`, every storage slot a tx changed as a `StateChange`, and fails
+    /// the whole bundle on the first revert/halt not covered by
+    /// `bundle.reverting_tx_hashes`.
+    async fn run_bundle(&self, bundle: &Bundle) -> anyhow::Result<SimulationResult> {
+        let Some(provider) = self.provider.clone() else {
+            anyhow::bail!("no provider configured; call set_provider before simulating a bundle");
+        };
+        let searcher = self.searcher_address.unwrap_or_default();
+        let searcher_revm = to_revm_address(searcher);
+
+        let mut db = ForkDb::new(provider, self.fork_block);
+        let starting_balance = db.basic(searcher_revm)?.map(|info| info.balance).unwrap_or_default();
+
+        let mut total_gas = 0u64;
+        let mut state_changes = Vec::new();
+
+        for tx in &bundle.transactions {
+            let (result, changes) = execute_tx(&mut db, tx, searcher)?;
+            state_changes.extend(changes);
+
+            let (gas_used, failure) = match result {
+                ExecutionResult::Success { gas_used, .. } => (gas_used, None),
+                ExecutionResult::Revert { gas_used, output } => {
+                    (gas_used, Some(format!("tx reverted: 0x{}", hex::encode(output))))
+                }
+                ExecutionResult::Halt { gas_used, reason } => {
+                    (gas_used, Some(format!("tx halted: {:?}", reason)))
+                }
+            };
+            total_gas += gas_used;
+
+            if let Some(error) = failure {
+                if !bundle_allows_revert(bundle, tx) {
+                    return Ok(SimulationResult {
+                        success: false,
+                        profit: 0,
+                        gas_used: total_gas,
+                        error: Some(error),
+                        state_changes,
+                    });
+                }
+            }
+        }
+
+        let ending_balance = db.basic(searcher_revm)?.map(|info| info.balance).unwrap_or_default();
+
+        Ok(SimulationResult {
+            success: true,
+            profit: balance_delta(starting_balance, ending_balance),
+            gas_used: total_gas,
+            error: None,
+            state_changes,
+        })
+    }
+
     pub async fn count(&self) -> u64 {
         self.count.load(Ordering::Relaxed)
     }
 }
 
+/// `true` if `tx`'s signed hash is in `bundle.reverting_tx_hashes` (i.e. this
+/// transaction is allowed to revert/halt without failing the bundle). A tx
+/// that hasn't been signed yet (`signed_raw: None`) can't be matched against
+/// a hash, so it's conservatively treated as not allowed.
+fn bundle_allows_revert(bundle: &Bundle, tx: &BundleTransaction) -> bool {
+    let Some(raw) = &tx.signed_raw else { return false };
+    let hash = ethers::utils::keccak256(raw);
+    bundle.reverting_tx_hashes.contains(&hash)
+}
+
+/// Execute one `BundleTransaction` against `db`, committing its state
+/// changes before returning so the next call in the same bundle observes
+/// them. Returns the execution outcome plus every storage slot the tx
+/// actually modified.
+fn execute_tx(
+    db: &mut ForkDb,
+    tx: &BundleTransaction,
+    caller: EthAddress,
+) -> anyhow::Result<(ExecutionResult, Vec<StateChange>)> {
+    let to: EthAddress = tx.to.parse()
+        .map_err(|e| anyhow::anyhow!("invalid `to` address {:?}: {}", tx.to, e))?;
+
+    let gas_price = tx.gas_price.or(tx.max_fee_per_gas).unwrap_or(0);
+    let value = tx.value;
+    let data = tx.data.clone();
+    let gas_limit = tx.gas_limit;
+    let nonce = tx.nonce;
+
+    let result_and_state = {
+        let mut evm = Evm::builder()
+            .with_db(&mut *db)
+            .modify_tx_env(|tx_env| {
+                tx_env.caller = to_revm_address(caller);
+                tx_env.transact_to = TransactTo::Call(to_revm_address(to));
+                tx_env.value = u128_to_revm_u256(value);
+                tx_env.data = data.into();
+                tx_env.gas_limit = gas_limit;
+                tx_env.gas_price = u128_to_revm_u256(gas_price);
+                tx_env.nonce = nonce;
+            })
+            .build();
+        evm.transact()?
+    };
+
+    let mut changes = Vec::new();
+    for (address, account) in result_and_state.state.iter() {
+        if !account.is_touched() {
+            continue;
+        }
+        for (slot, value) in account.storage.iter() {
+            if !value.is_changed() {
+                continue;
+            }
+            changes.push(StateChange {
+                address: to_eth_address(*address).0,
+                slot: u256_to_bytes(*slot),
+                old_value: u256_to_bytes(value.previous_or_original_value),
+                new_value: u256_to_bytes(value.present_value),
+            });
+        }
+    }
+
+    let result = result_and_state.result.clone();
+    db.commit(result_and_state.state);
+
+    Ok((result, changes))
+}
+
+/// Realized profit: the searcher's ending balance minus its starting
+/// balance, which can be negative if the bundle costs it more than it earns.
+fn balance_delta(before: RevmU256, after: RevmU256) -> i128 {
+    if after >= before {
+        i128::try_from(after - before).unwrap_or(i128::MAX)
+    } else {
+        -i128::try_from(before - after).unwrap_or(i128::MAX)
+    }
+}
+
+fn to_eth_address(address: RevmAddress) -> EthAddress {
+    EthAddress::from_slice(address.as_slice())
+}
+
+fn to_revm_address(address: EthAddress) -> RevmAddress {
+    RevmAddress::from_slice(address.as_bytes())
+}
+
+fn u128_to_revm_u256(value: u128) -> RevmU256 {
+    RevmU256::from(value)
+}
+
+fn u256_to_bytes(value: RevmU256) -> [u8; 32] {
+    value.to_be_bytes::<32>()
+}
+
+/// Executes `data` as a read-only call against `to`, forked from live state
+/// at `fork_block` (or "latest"), and returns the raw return bytes. Nothing
+/// is committed back to `db`, so this is safe to use for quoting against a
+/// contract's view/quoter functions — the `PoolManager` simulated-quoting
+/// backend's primitive for "actually run the pool's quoter calldata" rather
+/// than approximating it analytically.
+pub(crate) fn call_view(
+    provider: Arc<Provider<Http>>,
+    fork_block: Option<u64>,
+    to: EthAddress,
+    data: Vec<u8>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut db = ForkDb::new(provider, fork_block);
+    let caller = EthAddress::zero();
+
+    let result = {
+        let mut evm = Evm::builder()
+            .with_db(&mut db)
+            .modify_tx_env(|tx_env| {
+                tx_env.caller = to_revm_address(caller);
+                tx_env.transact_to = TransactTo::Call(to_revm_address(to));
+                tx_env.data = data.into();
+                tx_env.gas_limit = 5_000_000;
+            })
+            .build();
+        evm.transact()?.result
+    };
+
+    match result {
+        ExecutionResult::Success { output, .. } => Ok(output.into_data().to_vec()),
+        ExecutionResult::Revert { output, .. } => {
+            anyhow::bail!("call to {:?} reverted: 0x{}", to, hex::encode(output))
+        }
+        ExecutionResult::Halt { reason, .. } => {
+            anyhow::bail!("call to {:?} halted: {:?}", to, reason)
+        }
+    }
+}
+
+/// Lazily-populated revm `Database` backed by a live `Provider`, forked at
+/// `fork_block` (or "latest" if `None`). Reads that miss `accounts`/`storage`
+/// block on an RPC round-trip — revm's `Database` trait is synchronous, so
+/// this uses `block_in_place` plus the current Tokio handle, the same trick
+/// `AlloyDB` uses — and are cached so later reads in the same simulation,
+/// and writes from earlier transactions in the same bundle (via
+/// `DatabaseCommit`), are free.
+pub(crate) struct ForkDb {
+    provider: Arc<Provider<Http>>,
+    fork_block: Option<u64>,
+    accounts: HashMap<RevmAddress, AccountInfo>,
+    storage: HashMap<(RevmAddress, RevmU256), RevmU256>,
+}
+
+impl ForkDb {
+    fn new(provider: Arc<Provider<Http>>, fork_block: Option<u64>) -> Self {
+        Self {
+            provider,
+            fork_block,
+            accounts: HashMap::new(),
+            storage: HashMap::new(),
+        }
+    }
+
+    fn block_id(&self) -> Option<BlockId> {
+        self.fork_block.map(|b| BlockId::Number(BlockNumber::Number(b.into())))
+    }
+
+    fn fetch_account(&self, address: EthAddress) -> anyhow::Result<AccountInfo> {
+        let provider = self.provider.clone();
+        let block = self.block_id();
+
+        let (balance, nonce, code) = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async move {
+                let balance = provider.get_balance(address, block).await?;
+                let nonce = provider.get_transaction_count(address, block).await?;
+                let code = provider.get_code(address, block).await?;
+                anyhow::Ok((balance, nonce, code))
+            })
+        })?;
+
+        Ok(AccountInfo {
+            balance: eth_u256_to_revm(balance),
+            nonce: nonce.as_u64(),
+            code_hash: B256::from(ethers::utils::keccak256(&code)),
+            code: if code.0.is_empty() { None } else { Some(Bytecode::new_raw(code.0.into())) },
+        })
+    }
+
+    fn fetch_storage(&self, address: EthAddress, slot: EthH256) -> anyhow::Result<EthU256> {
+        let provider = self.provider.clone();
+        let block = self.block_id();
+        let value = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(provider.get_storage_at(address, slot, block))
+        })?;
+        Ok(EthU256::from_big_endian(value.as_bytes()))
+    }
+}
+
+fn eth_u256_to_revm(value: EthU256) -> RevmU256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    RevmU256::from_be_bytes(bytes)
+}
+
+impl Database for ForkDb {
+    type Error = anyhow::Error;
+
+    fn basic(&mut self, address: RevmAddress) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(info) = self.accounts.get(&address) {
+            return Ok(Some(info.clone()));
+        }
+        let info = self.fetch_account(to_eth_address(address))?;
+        self.accounts.insert(address, info.clone());
+        Ok(Some(info))
+    }
+
+    fn code_by_hash(&mut self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+        // Every account's code is already resolved by `basic`; revm only
+        // falls back to this for a hash it hasn't seen attached to an
+        // account, which shouldn't happen against this fork-only backend.
+        Ok(Bytecode::default())
+    }
+
+    fn storage(&mut self, address: RevmAddress, index: RevmU256) -> Result<RevmU256, Self::Error> {
+        if let Some(value) = self.storage.get(&(address, index)) {
+            return Ok(*value);
+        }
+        let slot = EthH256::from(u256_to_bytes(index));
+        let value = self.fetch_storage(to_eth_address(address), slot)?;
+        let revm_value = eth_u256_to_revm(value);
+        self.storage.insert((address, index), revm_value);
+        Ok(revm_value)
+    }
+
+    fn block_hash(&mut self, number: RevmU256) -> Result<B256, Self::Error> {
+        let provider = self.provider.clone();
+        let block_number: u64 = number.try_into().unwrap_or(u64::MAX);
+        let block = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(provider.get_block(block_number))
+        })?;
+        Ok(block.and_then(|b| b.hash).map(|h| B256::from(h.0)).unwrap_or_default())
+    }
+}
+
+impl DatabaseCommit for ForkDb {
+    fn commit(&mut self, changes: HashMap<RevmAddress, revm::primitives::Account>) {
+        for (address, account) in changes {
+            self.accounts.insert(address, account.info);
+            for (slot, value) in account.storage {
+                self.storage.insert((address, slot), value.present_value);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,7 +12,7 @@ pub enum OpportunityType {
 }
 
 /// DEX types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DexType {
     UniswapV2,
     UniswapV3,
@@ -61,7 +61,7 @@ pub struct Opportunity {
 }
 
 /// Simulation result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SimulationResult {
     pub success: bool,
     pub profit: i128,
@@ -71,7 +71,7 @@ pub struct SimulationResult {
 }
 
 /// State change from simulation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct StateChange {
     pub address: [u8; 20],
     pub slot: [u8; 32],
@@ -101,6 +101,9 @@ pub struct BundleTransaction {
     pub max_priority_fee_per_gas: Option<u128>,
     pub data: Vec<u8>,
     pub nonce: Option<u64>,
+    /// Raw `0x02`-prefixed signed transaction bytes, ready for relay submission.
+    /// `None` until a `TransactionSigner` has been applied.
+    pub signed_raw: Option<Vec<u8>>,
 }
 
 /// Bundle submission result